@@ -0,0 +1,141 @@
+use crate::geometry::Primitive;
+use crate::persist::Fingerprint;
+use crate::space::ConfigurationMap;
+use crate::structs3d::Point3;
+
+// Colisão de corpo inteiro (SCA) para manipuladores seriais: cada elo é o segmento
+// entre duas juntas consecutivas da cinemática direta, verificado contra os obstáculos
+// e o chão. O modo "baseline" do sca_rrt.py (só a garra) fica disponível para
+// comparação: com ele os caminhos podem atravessar obstáculos com o resto do braço.
+
+// Obstáculos da bancada, apoiados no chão (z = 0)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArmObstacle {
+    Cylinder { x: f64, y: f64, radius: f64, height: f64 },
+    // Caixa centrada em (x, y) no plano
+    Cuboid { x: f64, y: f64, size_x: f64, size_y: f64, size_z: f64 },
+}
+
+impl ArmObstacle {
+    // Sólido fechado, como os primitivos de geometry: a borda conta como obstáculo
+    pub fn contains(&self, p: &Point3) -> bool {
+        match *self {
+            ArmObstacle::Cylinder { x, y, radius, height } => (p.x - x).hypot(p.y - y) <= radius && p.z <= height,
+            ArmObstacle::Cuboid { x, y, size_x, size_y, size_z } => {
+                (p.x - x).abs() <= size_x / 2.0 && (p.y - y).abs() <= size_y / 2.0 && p.z <= size_z
+            }
+        }
+    }
+
+    // Tipo e dimensões, para a impressão digital do mapa
+    pub fn write_fingerprint(&self, hasher: &mut Fingerprint) {
+        let (tag, values): (&[u8], [f64; 5]) = match *self {
+            ArmObstacle::Cylinder { x, y, radius, height } => (b"cylinder", [x, y, radius, height, 0.0]),
+            ArmObstacle::Cuboid { x, y, size_x, size_y, size_z } => (b"cuboid", [x, y, size_x, size_y, size_z]),
+        };
+        hasher.write(tag);
+        for v in values {
+            hasher.write(&v.to_le_bytes());
+        }
+    }
+
+    // Mesmo sólido como primitivo do mundo geométrico
+    pub fn primitive(&self) -> Primitive {
+        match *self {
+            ArmObstacle::Cylinder { x, y, radius, height } => Primitive::vertical_cylinder(x, y, radius, height),
+            ArmObstacle::Cuboid { x, y, size_x, size_y, size_z } => Primitive::floor_box(x, y, size_x, size_y, size_z),
+        }
+    }
+
+    // Distância do ponto ao sólido (0 dentro ou na borda)
+    pub fn distance(&self, p: &Point3) -> f64 {
+        self.primitive().distance(p)
+    }
+
+    // Distância exata do segmento ab ao sólido
+    pub fn segment_distance(&self, a: &Point3, b: &Point3) -> f64 {
+        self.primitive().segment_distance(a, b)
+    }
+
+    // A cápsula de eixo ab e raio `radius` toca o sólido? A distância muda no máximo tanto
+    // quanto o ponto se move, então o meio do segmento descarta os casos distantes sem busca.
+    pub fn capsule_collides(&self, a: &Point3, b: &Point3, radius: f64) -> bool {
+        let middle = lerp(a, b, 0.5);
+        if self.distance(&middle) - a.dist(b) / 2.0 > radius {
+            return false;
+        }
+        self.segment_distance(a, b) <= radius
+    }
+}
+
+fn lerp(a: &Point3, b: &Point3, t: f64) -> Point3 {
+    Point3 { x: a.x + (b.x - a.x) * t, y: a.y + (b.y - a.y) * t, z: a.z + (b.z - a.z) * t }
+}
+
+// Como o corpo do braço é verificado
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArmCollisionMode {
+    // Baseline do sca_rrt.py: só a posição da garra (última junta)
+    Hand,
+    // SCA do sca_rrt.py: steps + 1 pontos interpolados em cada elo
+    Interpolated { steps: usize },
+    // Cada elo é uma cápsula (segmento engrossado por radius) testada de forma exata.
+    // Com radius = 0 é o próprio segmento; contato com a borda conta como colisão.
+    Capsule { radius: f64 },
+}
+
+impl ArmCollisionMode {
+    pub fn write_fingerprint(&self, hasher: &mut Fingerprint) {
+        match *self {
+            ArmCollisionMode::Hand => hasher.write(b"hand"),
+            ArmCollisionMode::Interpolated { steps } => {
+                hasher.write(b"interpolated");
+                hasher.write(&(steps as u64).to_le_bytes());
+            }
+            ArmCollisionMode::Capsule { radius } => {
+                hasher.write(b"capsule");
+                hasher.write(&radius.to_le_bytes());
+            }
+        }
+    }
+}
+
+impl Default for ArmCollisionMode {
+    fn default() -> Self {
+        ArmCollisionMode::Interpolated { steps: 8 }
+    }
+}
+
+fn is_point_colliding(obstacles: &[ArmObstacle], p: &Point3) -> bool {
+    p.z < 0.0 || obstacles.iter().any(|o| o.contains(p))
+}
+
+// Verifica a cadeia de juntas [base, ..., garra] de um manipulador serial. A base
+// está presa ao chão, então na cápsula o chão só é testado a partir da segunda junta.
+pub fn chain_collides(obstacles: &[ArmObstacle], joints: &[Point3], mode: ArmCollisionMode) -> bool {
+    match mode {
+        ArmCollisionMode::Hand => joints.last().is_some_and(|hand| is_point_colliding(obstacles, hand)),
+        ArmCollisionMode::Interpolated { steps } => {
+            let steps = steps.max(1);
+            joints.windows(2).any(|link| {
+                (0..=steps).any(|i| is_point_colliding(obstacles, &lerp(&link[0], &link[1], i as f64 / steps as f64)))
+            })
+        }
+        ArmCollisionMode::Capsule { radius } => {
+            joints.iter().skip(1).any(|p| p.z <= radius)
+                || joints.windows(2).any(|link| obstacles.iter().any(|o| o.capsule_collides(&link[0], &link[1], radius)))
+        }
+    }
+}
+
+// Índice da primeira aresta do caminho (entre path[i] e path[i + 1]) que não é livre no
+// mapa dado, com num_steps + 1 estados verificados por aresta. Serve para validar com
+// um verificador estrito (ex.: corpo inteiro) um caminho planejado com outro mais fraco.
+pub fn first_tunneling_edge<M: ConfigurationMap>(map: &M, path: &[M::State], num_steps: i32) -> Option<usize> {
+    path.windows(2).position(|edge| !map.is_motion_free(&edge[0], &edge[1], num_steps))
+}
+
+// is_path_tunneling do sca_rrt.py: o caminho atravessa algum obstáculo?
+pub fn is_path_tunneling<M: ConfigurationMap>(map: &M, path: &[M::State], num_steps: i32) -> bool {
+    first_tunneling_edge(map, path, num_steps).is_some()
+}
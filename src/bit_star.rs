@@ -0,0 +1,407 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::kdtree::KdTree;
+use crate::prm::HeapEntry;
+use rand::rngs::StdRng;
+
+use crate::sampler::*;
+use crate::space::*;
+use crate::tree::trace_to_root;
+
+const GOAL: usize = 1;
+
+// Aresta candidata na fila do BIT*, ordenada por g(v) + ĉ(v, x) + ĥ(x)
+#[derive(Clone, Copy, Debug)]
+struct EdgeEntry {
+    key: f64,
+    from: usize,
+    to: usize,
+}
+
+impl PartialEq for EdgeEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for EdgeEntry {}
+
+impl PartialOrd for EdgeEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EdgeEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.total_cmp(&self.key)
+    }
+}
+
+// BIT* (Batch Informed Trees): amostragem em lotes com busca ordenada por heurística.
+// Cada lote melhora (ou mantém) a solução, então o planejador é "anytime":
+// run_batch() pode ser chamado até o tempo acabar.
+// Simplificação: a poda remove só amostras não conectadas, os vértices da árvore ficam.
+pub struct BITStarPlanner<M: ConfigurationMap> {
+    map: M,
+    space: M::Space,
+    sampler: Box<dyn Sampler<M>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    start: M::State,
+    goal: M::State,
+    batch_size: usize,
+    max_batches: usize,
+    radius_multiplier: f64,
+    num_collision_check_steps: i32,
+
+    // Todos os estados (vértices da árvore e amostras) ficam no mesmo vetor;
+    // o início é o índice 0 e o objetivo o índice 1
+    pub states: Vec<M::State>,
+    index: KdTree<M::State>, // Índice espacial de states, na mesma ordem
+    pub parent: Vec<Option<usize>>,
+    pub g: Vec<f64>, // Custo desde o início (infinito fora da árvore)
+    in_tree: Vec<bool>,
+    pruned: Vec<bool>,
+    old: Vec<bool>,    // Vértice já existia no início do lote
+    queued: Vec<bool>, // Vértice está na fila de vértices
+    children: Vec<Vec<usize>>,
+
+    vertex_queue: BinaryHeap<HeapEntry>,
+    edge_queue: BinaryHeap<EdgeEntry>,
+    edge_cache: HashMap<(usize, usize), bool>,
+
+    radius: f64,
+    batches: usize,
+    collision_checks: usize,
+    history: Vec<(usize, f64)>, // (lote, custo) a cada melhora da solução
+    path_found: Option<Vec<M::State>>,
+}
+
+impl<M: ConfigurationMap> BITStarPlanner<M> {
+    pub fn new(map: M, start: M::State, goal: M::State, batch_size: usize, num_collision_check_steps: i32) -> Self {
+        let space = map.space();
+        Self {
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            start,
+            goal,
+            batch_size,
+            max_batches: 20,
+            radius_multiplier: 1.1,
+            num_collision_check_steps: num_collision_check_steps.max(1),
+            states: vec![start, goal],
+            index: KdTree::from_points(vec![start, goal]),
+            parent: vec![None, None],
+            g: vec![0.0, f64::INFINITY],
+            in_tree: vec![true, false],
+            pruned: vec![false, false],
+            old: vec![false, false],
+            queued: vec![false, false],
+            children: vec![Vec::new(), Vec::new()],
+            vertex_queue: BinaryHeap::new(),
+            edge_queue: BinaryHeap::new(),
+            edge_cache: HashMap::new(),
+            radius: f64::INFINITY,
+            batches: 0,
+            collision_checks: 0,
+            history: Vec::new(),
+            path_found: None,
+        }
+    }
+
+    pub fn with_max_batches(mut self, max_batches: usize) -> Self {
+        self.max_batches = max_batches;
+        self
+    }
+
+    // Fonte das amostras antes da primeira solução; depois dela as amostras
+    // vêm do subconjunto informado do espaço (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<M> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    pub fn with_radius_multiplier(mut self, multiplier: f64) -> Self {
+        self.radius_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    // Heurísticas admissíveis: distância do espaço (linha reta no caso euclidiano)
+    fn g_hat(&self, i: usize) -> f64 {
+        self.space.distance(&self.start, &self.states[i])
+    }
+
+    fn h_hat(&self, i: usize) -> f64 {
+        self.space.distance(&self.states[i], &self.goal)
+    }
+
+    pub fn best_cost(&self) -> f64 {
+        self.g[GOAL]
+    }
+
+    fn push_state(&mut self, q: M::State) {
+        self.states.push(q);
+        self.index.insert(q);
+        self.parent.push(None);
+        self.g.push(f64::INFINITY);
+        self.in_tree.push(false);
+        self.pruned.push(false);
+        self.old.push(false);
+        self.queued.push(false);
+        self.children.push(Vec::new());
+    }
+
+    // Remove amostras que não podem melhorar a solução atual
+    fn prune(&mut self) {
+        let c_best = self.best_cost();
+        if !c_best.is_finite() {
+            return;
+        }
+        for i in 0..self.states.len() {
+            if !self.in_tree[i] && !self.pruned[i] && i != GOAL && self.g_hat(i) + self.h_hat(i) >= c_best {
+                self.pruned[i] = true;
+            }
+        }
+    }
+
+    // Novo lote de amostras livres; depois da primeira solução, só no subconjunto informado
+    fn add_samples(&mut self) {
+        let c_best = self.best_cost();
+        // Solução já na reta start-goal: nenhuma amostra pode melhorá-la
+        if self.is_optimal(c_best) {
+            return;
+        }
+        let mut added = 0;
+        let mut attempts = 0;
+        while added < self.batch_size && attempts < 100 * self.batch_size {
+            attempts += 1;
+            let q = if c_best.is_finite() {
+                self.space.sample_informed(&self.start, &self.goal, c_best, &mut self.rng)
+            } else {
+                self.sampler.sample(&self.map, &self.space, &mut self.rng)
+            };
+            if self.space.distance(&self.start, &q) + self.space.distance(&q, &self.goal) >= c_best || !self.map.is_free(&q) {
+                continue;
+            }
+            self.push_state(q);
+            added += 1;
+        }
+    }
+
+    fn update_radius(&mut self) {
+        let d = M::State::DIM as f64;
+        let q = self.pruned.iter().filter(|&&p| !p).count().max(2) as f64;
+        self.radius = self.radius_multiplier
+            * 2.0
+            * (1.0 + 1.0 / d).powf(1.0 / d)
+            * (self.space.volume() / unit_ball_volume(M::State::DIM)).powf(1.0 / d)
+            * (q.ln() / q).powf(1.0 / d);
+    }
+
+    fn queue_vertex(&mut self, v: usize) {
+        if !self.queued[v] {
+            self.queued[v] = true;
+            self.vertex_queue.push(HeapEntry { cost: self.g[v] + self.h_hat(v), idx: v });
+        }
+    }
+
+    // Gera as arestas candidatas saindo de v
+    fn expand_vertex(&mut self, v: usize) {
+        let c_best = self.best_cost();
+        let q = self.states[v];
+
+        for (i, d) in self.index.within_radius(&self.space, &q, self.radius) {
+            if i == v || self.pruned[i] {
+                continue;
+            }
+
+            if !self.in_tree[i] {
+                if self.g_hat(v) + d + self.h_hat(i) < c_best {
+                    self.edge_queue.push(EdgeEntry { key: self.g[v] + d + self.h_hat(i), from: v, to: i });
+                }
+            } else if !self.old[v]
+                && self.parent[v] != Some(i)
+                && self.parent[i] != Some(v)
+                && self.g_hat(v) + d + self.h_hat(i) < c_best
+                && self.g[v] + d < self.g[i]
+            {
+                // Arestas de religamento só saem de vértices novos neste lote
+                self.edge_queue.push(EdgeEntry { key: self.g[v] + d + self.h_hat(i), from: v, to: i });
+            }
+        }
+    }
+
+    fn is_edge_free(&mut self, a: usize, b: usize) -> bool {
+        let key = if a < b { (a, b) } else { (b, a) };
+        if let Some(&free) = self.edge_cache.get(&key) {
+            return free;
+        }
+        self.collision_checks += 1;
+        let free = self.map.is_motion_free(&self.states[a], &self.states[b], self.num_collision_check_steps);
+        self.edge_cache.insert(key, free);
+        free
+    }
+
+    fn set_parent(&mut self, x: usize, v: usize) {
+        if let Some(old_parent) = self.parent[x] {
+            self.children[old_parent].retain(|&c| c != x);
+        }
+        self.parent[x] = Some(v);
+        self.children[v].push(x);
+        self.in_tree[x] = true;
+
+        // Propaga o novo custo para a subárvore
+        let mut stack = vec![x];
+        while let Some(i) = stack.pop() {
+            let p = self.parent[i].unwrap();
+            self.g[i] = self.g[p] + self.space.distance(&self.states[p], &self.states[i]);
+            stack.extend(self.children[i].iter().copied());
+        }
+    }
+
+    // Menor chave válida da fila de vértices (descarta entradas desatualizadas)
+    fn peek_vertex_key(&mut self) -> Option<f64> {
+        while let Some(&HeapEntry { cost, idx }) = self.vertex_queue.peek() {
+            let key = self.g[idx] + self.h_hat(idx);
+            if key < cost - 1e-9 {
+                self.vertex_queue.pop();
+                self.vertex_queue.push(HeapEntry { cost: key, idx });
+                continue;
+            }
+            return Some(cost);
+        }
+        None
+    }
+
+    fn peek_edge_key(&self) -> f64 {
+        self.edge_queue.peek().map_or(f64::INFINITY, |e| e.key)
+    }
+
+    // Processa um lote completo; retorna o melhor custo até agora
+    pub fn run_batch(&mut self) -> Option<f64> {
+        if self.batches == 0 && (!self.map.is_free(&self.start) || !self.map.is_free(&self.goal)) {
+            return None;
+        }
+        self.batches += 1;
+        let cost_before = self.best_cost();
+
+        self.prune();
+        self.add_samples();
+        self.update_radius();
+
+        self.edge_queue.clear();
+        self.vertex_queue.clear();
+        for v in 0..self.states.len() {
+            self.queued[v] = false;
+            if self.in_tree[v] {
+                self.old[v] = true;
+            }
+        }
+        for v in 0..self.states.len() {
+            if self.in_tree[v] {
+                self.queue_vertex(v);
+            }
+        }
+
+        loop {
+            // Expande vértices enquanto eles podem gerar arestas melhores que a melhor da fila
+            while let Some(vertex_key) = self.peek_vertex_key() {
+                if vertex_key > self.peek_edge_key() {
+                    break;
+                }
+                let HeapEntry { idx: v, .. } = self.vertex_queue.pop().unwrap();
+                self.queued[v] = false;
+                self.expand_vertex(v);
+            }
+
+            let Some(EdgeEntry { from: v, to: x, .. }) = self.edge_queue.pop() else {
+                break;
+            };
+
+            let c_best = self.best_cost();
+            let c_hat = self.space.distance(&self.states[v], &self.states[x]);
+            // Nenhuma aresta restante pode melhorar a solução: fim do lote
+            if self.g[v] + c_hat + self.h_hat(x) >= c_best {
+                self.edge_queue.clear();
+                self.vertex_queue.clear();
+                break;
+            }
+            if self.g[v] + c_hat >= self.g[x] {
+                continue;
+            }
+            if !self.is_edge_free(v, x) {
+                continue;
+            }
+            // Custo real da aresta reta é a própria distância
+            if self.g_hat(v) + c_hat + self.h_hat(x) >= c_best || self.g[v] + c_hat >= self.g[x] {
+                continue;
+            }
+
+            let is_new = !self.in_tree[x];
+            self.set_parent(x, v);
+            if is_new {
+                self.old[x] = false;
+                self.queue_vertex(x);
+            }
+        }
+
+        let c_best = self.best_cost();
+        if c_best < cost_before {
+            self.history.push((self.batches, c_best));
+            self.path_found = Some(self.extract_path());
+        }
+        c_best.is_finite().then_some(c_best)
+    }
+
+    fn extract_path(&self) -> Vec<M::State> {
+        trace_to_root(GOAL, |i| self.parent[i]).into_iter().map(|i| self.states[i]).collect()
+    }
+
+    fn is_optimal(&self, c_best: f64) -> bool {
+        c_best <= self.space.distance(&self.start, &self.goal) + 1e-9
+    }
+
+    // Roda max_batches lotes (ou até atingir o custo da reta) e retorna o melhor caminho encontrado
+    pub fn plan(&mut self) -> Option<&Vec<M::State>> {
+        for _ in 0..self.max_batches {
+            if self.run_batch().is_some_and(|c| self.is_optimal(c)) {
+                break;
+            }
+        }
+        self.path_found.as_ref()
+    }
+
+    pub fn path(&self) -> Option<&Vec<M::State>> {
+        self.path_found.as_ref()
+    }
+
+    // Histórico de melhoras: (número do lote, custo da solução)
+    pub fn solution_history(&self) -> &[(usize, f64)] {
+        &self.history
+    }
+
+    // Vértices na árvore atual (as amostras ainda soltas não contam)
+    pub fn node_count(&self) -> usize {
+        self.in_tree.iter().filter(|&&t| t).count()
+    }
+
+    pub fn collision_checks(&self) -> usize {
+        self.collision_checks
+    }
+
+    pub fn map(&self) -> &M {
+        &self.map
+    }
+}
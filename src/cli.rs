@@ -0,0 +1,381 @@
+use std::collections::HashMap;
+use std::io;
+use std::str::FromStr;
+
+use crate::render::render_map;
+use crate::roarm::ArmMap;
+use crate::scene::*;
+
+// Interface de linha de comando dos planejadores. Códigos de saída: 0 sucesso,
+// 1 erro (argumentos, arquivos, cena inválida), 2 planejamento sem caminho.
+
+pub const USAGE: &str = "\
+Uso:
+  sampling-path-planning plan <2d|3d|arm> [opções]
+  sampling-path-planning benchmark <2d|3d|arm> [opções] [--runs N] [--csv ARQUIVO]
+  sampling-path-planning render --map IMAGEM [--nodes CSV] [--path CSV] --out IMAGEM
+
+Opções de plan e benchmark:
+  --scene ARQUIVO         cena TOML/JSON usada como base (as demais opções a sobrescrevem)
+  --map MAPA              imagem (2d), imagem ou lista a.png,b.png de camadas (3d),
+                          simple ou dense (arm)
+  --depth N, --dz F       camadas e espessura quando uma única imagem vira mapa 3D
+  --start x,y[,...]       estado inicial (arm: 4 juntas em radianos)
+  --goal x,y[,...]        objetivo (arm: ponto XYZ da garra em metros)
+  --planner NOME          rrt, rrt_star, informed_rrt_star, rrt_connect, prm, lazy_prm,
+                          fmt_star, bit_star, est, kpiece, kinodynamic_rrt ou sst
+                          (arm: só rrt)
+  --seed N                semente (benchmark: semente da primeira execução)
+  --iterations N          máximo de iterações
+  --step-size F, --goal-radius F, --collision-steps N
+  --samples N             prm, lazy_prm e fmt_star: amostras; bit_star: amostras por lote
+  --batches N             bit_star: máximo de lotes
+  --dynamics MODELO       kinodynamic_rrt e sst: double_integrator ou unicycle (só 2d)
+  --dt F, --max-speed F, --max-accel F, --max-turn-rate F
+                          passo de integração (s) e limites do modelo dinâmico
+  --collision MODO        arm: hand, interpolated ou capsule
+  --nodes ARQUIVO, --path ARQUIVO
+                          CSVs da árvore e do caminho (só em plan; prm grava o roadmap,
+                          fmt_star e bit_star não gravam nós)
+  --runs N, --csv ARQUIVO benchmark: execuções (padrão 10) e CSV com uma linha por execução
+
+Códigos de saída: 0 sucesso, 1 erro, 2 nenhum caminho encontrado.
+";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Problem {
+    Plan2D,
+    Plan3D,
+    Arm,
+}
+
+fn usage_error(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg.into())
+}
+
+// Opções no formato --nome valor, todas com valor
+struct Options {
+    values: HashMap<String, String>,
+}
+
+impl Options {
+    fn parse(args: &[String], allowed: &[&str]) -> io::Result<Options> {
+        let mut values = HashMap::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                return Err(usage_error(format!("Argumento inesperado: {}", arg)));
+            };
+            if !allowed.contains(&name) {
+                return Err(usage_error(format!("Opção desconhecida: --{}", name)));
+            }
+            let Some(value) = iter.next() else {
+                return Err(usage_error(format!("Falta o valor de --{}", name)));
+            };
+            values.insert(name.to_string(), value.clone());
+        }
+        Ok(Options { values })
+    }
+
+    fn str(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    fn get<T: FromStr>(&self, name: &str) -> io::Result<Option<T>> {
+        self.str(name)
+            .map(|v| v.parse().map_err(|_| usage_error(format!("Valor inválido para --{}: {}", name, v))))
+            .transpose()
+    }
+
+    fn coords(&self, name: &str) -> io::Result<Option<Vec<f64>>> {
+        self.str(name)
+            .map(|v| {
+                v.split(',')
+                    .map(|c| c.trim().parse().map_err(|_| usage_error(format!("Coordenada inválida em --{}: {}", name, v))))
+                    .collect()
+            })
+            .transpose()
+    }
+}
+
+const PLAN_OPTIONS: [&str; 22] = [
+    "scene", "map", "depth", "dz", "start", "goal", "planner", "seed", "iterations", "step-size", "goal-radius",
+    "collision-steps", "samples", "batches", "dynamics", "dt", "max-speed", "max-accel", "max-turn-rate", "collision",
+    "nodes", "path",
+];
+
+// Cena padrão de cada problema: os exemplos de scenes/ sem arquivos de saída
+fn default_scene(problem: Problem) -> Scene {
+    let planner = PlannerParams::default();
+    match problem {
+        Problem::Plan2D => Scene {
+            map: MapSource::Image { path: "data/map.jpg".to_string() },
+            start: vec![50.0, 50.0],
+            goal: vec![700.0, 500.0],
+            robot: RobotModel::Point,
+            planner: PlannerParams { max_iter: 10_000, collision_steps: 10, ..planner },
+            output: OutputFiles::default(),
+        },
+        Problem::Plan3D => Scene {
+            map: MapSource::Replicated { path: "data/map.jpg".to_string(), depth: 20, dz: 1.0 },
+            start: vec![50.0, 50.0, 0.0],
+            goal: vec![700.0, 500.0, 19.0],
+            robot: RobotModel::Point,
+            planner,
+            output: OutputFiles::default(),
+        },
+        Problem::Arm => Scene {
+            map: arm_map_source(&ArmMap::simple()),
+            start: vec![0.0; 4],
+            goal: vec![0.30, 0.25, 0.15],
+            robot: RobotModel::Arm { collision: ArmCollision::Interpolated, link_steps: 8, link_radius: 0.0 },
+            planner: PlannerParams { step_size: 0.1, goal_radius: 0.05, collision_steps: 10, ..planner },
+            output: OutputFiles::default(),
+        },
+    }
+}
+
+fn arm_map_source(map: &ArmMap) -> MapSource {
+    MapSource::Arm { obstacles: map.obstacles.iter().map(ArmObstacleSpec::from_obstacle).collect() }
+}
+
+fn matches_problem(map: &MapSource, problem: Problem) -> bool {
+    match map {
+        MapSource::Image { .. } => problem == Problem::Plan2D,
+        MapSource::Replicated { .. } | MapSource::Layers { .. } | MapSource::Primitives { .. } => problem == Problem::Plan3D,
+        MapSource::Arm { .. } => problem == Problem::Arm,
+    }
+}
+
+// Cena base (padrão ou --scene) com as opções aplicadas por cima
+fn build_scene(problem: Problem, opts: &Options) -> io::Result<Scene> {
+    let mut scene = match opts.str("scene") {
+        Some(file) => {
+            let scene = Scene::load(file)?;
+            if !matches_problem(&scene.map, problem) {
+                return Err(usage_error(format!("A cena {} não é um problema do tipo pedido", file)));
+            }
+            scene
+        }
+        None => default_scene(problem),
+    };
+
+    if let Some(map) = opts.str("map") {
+        scene.map = match problem {
+            Problem::Plan2D => MapSource::Image { path: map.to_string() },
+            Problem::Plan3D if map.contains(',') => {
+                let dz = opts.get("dz")?.unwrap_or(1.0);
+                MapSource::Layers { paths: map.split(',').map(str::to_string).collect(), dz }
+            }
+            Problem::Plan3D => {
+                let depth = opts.get("depth")?.unwrap_or(20);
+                let dz = opts.get("dz")?.unwrap_or(1.0);
+                MapSource::Replicated { path: map.to_string(), depth, dz }
+            }
+            Problem::Arm => match map {
+                "simple" => arm_map_source(&ArmMap::simple()),
+                "dense" => arm_map_source(&ArmMap::dense()),
+                other => return Err(usage_error(format!("Bancada desconhecida: {} (use simple ou dense)", other))),
+            },
+        };
+    } else if let MapSource::Replicated { depth, dz, .. } = &mut scene.map {
+        *depth = opts.get("depth")?.unwrap_or(*depth);
+        *dz = opts.get("dz")?.unwrap_or(*dz);
+    }
+
+    if let Some(start) = opts.coords("start")? {
+        scene.start = start;
+    }
+    if let Some(goal) = opts.coords("goal")? {
+        scene.goal = goal;
+    }
+
+    let p = &mut scene.planner;
+    if let Some(name) = opts.str("planner") {
+        p.algorithm = match name {
+            "rrt" => Algorithm::Rrt,
+            "rrt_star" => Algorithm::RrtStar,
+            "informed_rrt_star" => Algorithm::InformedRrtStar,
+            "rrt_connect" => Algorithm::RrtConnect,
+            "prm" => Algorithm::Prm,
+            "lazy_prm" => Algorithm::LazyPrm,
+            "fmt_star" => Algorithm::FmtStar,
+            "bit_star" => Algorithm::BitStar,
+            "est" => Algorithm::Est,
+            "kpiece" => Algorithm::Kpiece,
+            "kinodynamic_rrt" => Algorithm::KinodynamicRrt,
+            "sst" => Algorithm::Sst,
+            other => return Err(usage_error(format!("Planejador desconhecido: {}", other))),
+        };
+    }
+    p.seed = opts.get("seed")?.or(p.seed);
+    p.max_iter = opts.get("iterations")?.unwrap_or(p.max_iter);
+    p.step_size = opts.get("step-size")?.unwrap_or(p.step_size);
+    p.goal_radius = opts.get("goal-radius")?.unwrap_or(p.goal_radius);
+    p.collision_steps = opts.get("collision-steps")?.unwrap_or(p.collision_steps);
+    p.num_samples = opts.get("samples")?.unwrap_or(p.num_samples);
+    p.max_batches = opts.get("batches")?.unwrap_or(p.max_batches);
+    p.dt = opts.get("dt")?.unwrap_or(p.dt);
+    if let Some(model) = opts.str("dynamics") {
+        p.dynamics = match model {
+            "double_integrator" => DynamicsModel::default(),
+            "unicycle" => DynamicsModel::Unicycle { min_speed: 0.0, max_speed: 50.0, max_turn_rate: 1.0 },
+            other => return Err(usage_error(format!("Modelo dinâmico desconhecido: {}", other))),
+        };
+    }
+    match &mut p.dynamics {
+        DynamicsModel::DoubleIntegrator { max_speed, max_accel } => {
+            if opts.str("max-turn-rate").is_some() {
+                return Err(usage_error("--max-turn-rate só vale para --dynamics unicycle"));
+            }
+            *max_speed = opts.get("max-speed")?.unwrap_or(*max_speed);
+            *max_accel = opts.get("max-accel")?.unwrap_or(*max_accel);
+        }
+        DynamicsModel::Unicycle { max_speed, max_turn_rate, .. } => {
+            if opts.str("max-accel").is_some() {
+                return Err(usage_error("--max-accel só vale para --dynamics double_integrator"));
+            }
+            *max_speed = opts.get("max-speed")?.unwrap_or(*max_speed);
+            *max_turn_rate = opts.get("max-turn-rate")?.unwrap_or(*max_turn_rate);
+        }
+    }
+
+    if let Some(mode) = opts.str("collision") {
+        if problem != Problem::Arm {
+            return Err(usage_error("--collision só vale para o braço (plan arm)"));
+        }
+        let collision = match mode {
+            "hand" => ArmCollision::Hand,
+            "interpolated" => ArmCollision::Interpolated,
+            "capsule" => ArmCollision::Capsule,
+            other => return Err(usage_error(format!("Modo de colisão desconhecido: {}", other))),
+        };
+        scene.robot = match scene.robot {
+            RobotModel::Arm { link_steps, link_radius, .. } => RobotModel::Arm { collision, link_steps, link_radius },
+            _ => RobotModel::Arm { collision, link_steps: 8, link_radius: 0.0 },
+        };
+    }
+
+    if let Some(nodes) = opts.str("nodes") {
+        scene.output.nodes = Some(nodes.to_string());
+    }
+    if let Some(path) = opts.str("path") {
+        scene.output.path = Some(path.to_string());
+    }
+    scene.validate()?;
+    Ok(scene)
+}
+
+fn parse_problem(args: &[String]) -> io::Result<(Problem, &[String])> {
+    let Some((kind, rest)) = args.split_first() else {
+        return Err(usage_error("Falta o tipo de problema: 2d, 3d ou arm"));
+    };
+    let problem = match kind.as_str() {
+        "2d" => Problem::Plan2D,
+        "3d" => Problem::Plan3D,
+        "arm" => Problem::Arm,
+        other => return Err(usage_error(format!("Tipo de problema desconhecido: {} (use 2d, 3d ou arm)", other))),
+    };
+    Ok((problem, rest))
+}
+
+fn plan(args: &[String]) -> io::Result<bool> {
+    let (problem, rest) = parse_problem(args)?;
+    let opts = Options::parse(rest, &PLAN_OPTIONS)?;
+    build_scene(problem, &opts)?.run()
+}
+
+// Executa a mesma cena com sementes consecutivas e resume sucesso, tempo, custo e nós
+fn benchmark(args: &[String]) -> io::Result<bool> {
+    let (problem, rest) = parse_problem(args)?;
+    let allowed: Vec<&str> = PLAN_OPTIONS.iter().copied().filter(|o| !["nodes", "path"].contains(o)).chain(["runs", "csv"]).collect();
+    let opts = Options::parse(rest, &allowed)?;
+    let runs: u64 = opts.get("runs")?.unwrap_or(10);
+    if runs == 0 {
+        return Err(usage_error("--runs deve ser pelo menos 1"));
+    }
+    let csv_file = opts.str("csv");
+    let mut scene = build_scene(problem, &opts)?;
+    scene.output = OutputFiles::default();
+    let first_seed = scene.planner.seed.unwrap_or(0);
+
+    let mut writer = csv_file.map(csv::Writer::from_path).transpose()?;
+    if let Some(w) = writer.as_mut() {
+        w.write_record(["run", "seed", "success", "time_s", "cost", "nodes"])?;
+    }
+
+    let mut results = Vec::new();
+    for run in 0..runs {
+        // Sementes consecutivas, dando a volta no fim do u64
+        let seed = first_seed.wrapping_add(run);
+        scene.planner.seed = Some(seed);
+        let outcome = scene.solve()?;
+        if let Some(w) = writer.as_mut() {
+            w.write_record(&[
+                run.to_string(),
+                seed.to_string(),
+                outcome.found().to_string(),
+                outcome.seconds.to_string(),
+                outcome.cost.map_or("NaN".to_string(), |c| c.to_string()),
+                outcome.nodes.to_string(),
+            ])?;
+        }
+        results.push(outcome);
+    }
+    if let Some(w) = writer.as_mut() {
+        w.flush()?;
+    }
+
+    let successes: Vec<&Outcome> = results.iter().filter(|o| o.found()).collect();
+    let mean = |values: Vec<f64>| if values.is_empty() { f64::NAN } else { values.iter().sum::<f64>() / values.len() as f64 };
+    println!("Planejador: {:?} | execuções: {}", scene.planner.algorithm, runs);
+    println!("Sucessos: {}/{}", successes.len(), runs);
+    println!("Tempo médio: {:.4} s", mean(results.iter().map(|o| o.seconds).collect()));
+    println!("Nós médios: {:.1}", mean(results.iter().map(|o| o.nodes as f64).collect()));
+    println!("Custo médio (sucessos): {:.4}", mean(successes.iter().filter_map(|o| o.cost).collect()));
+    if let Some(file) = csv_file {
+        println!("Resultados salvos em {}", file);
+    }
+    Ok(!successes.is_empty())
+}
+
+fn render(args: &[String]) -> io::Result<bool> {
+    let opts = Options::parse(args, &["map", "nodes", "path", "out"])?;
+    let map = opts.str("map").ok_or_else(|| usage_error("Falta --map"))?;
+    let out = opts.str("out").ok_or_else(|| usage_error("Falta --out"))?;
+    if opts.str("nodes").is_none() && opts.str("path").is_none() {
+        return Err(usage_error("Informe --nodes e/ou --path"));
+    }
+    render_map(map, opts.str("nodes"), opts.str("path"), out)?;
+    println!("Figura salva em {}", out);
+    Ok(true)
+}
+
+// Executa a linha de comando (sem o nome do programa) e devolve o código de saída
+pub fn run(args: &[String]) -> i32 {
+    let Some((command, rest)) = args.split_first() else {
+        eprint!("{}", USAGE);
+        return 1;
+    };
+    let result = match command.as_str() {
+        "plan" => plan(rest),
+        "benchmark" => benchmark(rest),
+        "render" => render(rest),
+        "help" | "--help" | "-h" => {
+            print!("{}", USAGE);
+            return 0;
+        }
+        other => Err(usage_error(format!("Comando desconhecido: {}", other))),
+    };
+    match result {
+        Ok(true) => 0,
+        Ok(false) => 2,
+        Err(e) => {
+            eprintln!("Erro: {}", e);
+            if e.kind() == io::ErrorKind::InvalidInput {
+                eprintln!("Use \"help\" para ver as opções.");
+            }
+            1
+        }
+    }
+}
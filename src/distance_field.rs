@@ -0,0 +1,252 @@
+use rand::RngCore;
+
+use crate::readmap::*;
+use crate::readmap3d::OccupancyMap3D;
+use crate::sampler::{MAX_ATTEMPTS, Sampler};
+use crate::space::*;
+
+// Valor "infinito" da transformada antes da raiz (finito para não gerar NaN nas parábolas)
+const FAR: f64 = 1e20;
+
+// Transformada de distância euclidiana exata de uma grade de ocupação (2D ou 3D):
+// para cada célula, a distância até a célula de obstáculo mais próxima, nas unidades
+// do estado (pixels em x/y, z contínuo no mapa 3D). Só as células de obstáculo contam,
+// a borda da grade não.
+#[derive(Clone, Debug)]
+pub struct DistanceField {
+    dims: Vec<usize>,
+    spacing: Vec<f64>, // Tamanho da célula em cada eixo
+    dist: Vec<f64>,    // Índice plano: x + nx * (y + ny * z)
+}
+
+impl DistanceField {
+    // Transformada de uma grade com as células ocupadas marcadas como true
+    pub fn from_occupancy(dims: Vec<usize>, spacing: Vec<f64>, occupied: &[bool]) -> Self {
+        assert_eq!(dims.len(), spacing.len(), "dims e spacing devem ter o mesmo número de eixos");
+        assert_eq!(dims.iter().product::<usize>(), occupied.len(), "Tamanho da grade não bate com dims");
+
+        let mut dist: Vec<f64> = occupied.iter().map(|&o| if o { 0.0 } else { FAR }).collect();
+
+        // Transformada separável (Felzenszwalb e Huttenlocher): uma passada 1D por eixo
+        // sobre as distâncias ao quadrado
+        let mut stride = 1;
+        for axis in 0..dims.len() {
+            let n = dims[axis];
+            let mut line = vec![0.0; n];
+            for start in 0..dist.len() {
+                // Início de cada linha ao longo do eixo: coordenada do eixo igual a 0
+                if (start / stride) % n != 0 {
+                    continue;
+                }
+                for (i, v) in line.iter_mut().enumerate() {
+                    *v = dist[start + i * stride];
+                }
+                let transformed = edt_1d(&line, spacing[axis]);
+                for (i, v) in transformed.into_iter().enumerate() {
+                    dist[start + i * stride] = v;
+                }
+            }
+            stride *= n;
+        }
+
+        for d in &mut dist {
+            *d = d.sqrt();
+        }
+        DistanceField { dims, spacing, dist }
+    }
+
+    pub fn dims(&self) -> &[usize] {
+        &self.dims
+    }
+
+    // Distâncias de todas as células, em ordem x, depois y, depois z
+    pub fn values(&self) -> &[f64] {
+        &self.dist
+    }
+
+    pub fn max_clearance(&self) -> f64 {
+        self.dist.iter().copied().fold(0.0, f64::max)
+    }
+
+    fn cell_of(&self, coords: &[f64]) -> Vec<usize> {
+        (0..self.dims.len())
+            .map(|axis| (coords[axis] / self.spacing[axis]).round().clamp(0.0, (self.dims[axis] - 1) as f64) as usize)
+            .collect()
+    }
+
+    fn at(&self, cell: &[usize]) -> f64 {
+        let mut idx = 0;
+        for axis in (0..self.dims.len()).rev() {
+            idx = idx * self.dims[axis] + cell[axis];
+        }
+        self.dist[idx]
+    }
+
+    // O ponto está dentro da grade? Fora dela clearance repete a célula da borda
+    fn in_grid(&self, coords: &[f64]) -> bool {
+        (0..self.dims.len()).all(|axis| {
+            let cell = coords[axis] / self.spacing[axis];
+            cell >= -0.5 && cell < self.dims[axis] as f64 - 0.5
+        })
+    }
+
+    // Distância ao obstáculo mais próximo na célula que contém o ponto
+    pub fn clearance(&self, coords: &[f64]) -> f64 {
+        self.at(&self.cell_of(coords))
+    }
+
+    // Gradiente da distância por diferenças centrais: aponta para longe do obstáculo mais próximo
+    pub fn gradient(&self, coords: &[f64]) -> Vec<f64> {
+        let cell = self.cell_of(coords);
+        (0..self.dims.len())
+            .map(|axis| {
+                let lo = cell[axis].saturating_sub(1);
+                let hi = (cell[axis] + 1).min(self.dims[axis] - 1);
+                if hi == lo {
+                    return 0.0;
+                }
+                let mut a = cell.clone();
+                let mut b = cell.clone();
+                a[axis] = lo;
+                b[axis] = hi;
+                (self.at(&b) - self.at(&a)) / ((hi - lo) as f64 * self.spacing[axis])
+            })
+            .collect()
+    }
+}
+
+// Transformada 1D sobre distâncias ao quadrado com espaçamento h (envelope inferior de parábolas)
+fn edt_1d(f: &[f64], h: f64) -> Vec<f64> {
+    let n = f.len();
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut d = vec![0.0; n];
+    let mut v = vec![0usize; n]; // Células cujas parábolas formam o envelope
+    let mut z = vec![0.0; n + 1]; // Fronteiras entre as parábolas
+    let pos = |i: usize| i as f64 * h;
+    let intersect = |q: usize, p: usize| ((f[q] + pos(q).powi(2)) - (f[p] + pos(p).powi(2))) / (2.0 * (pos(q) - pos(p)));
+
+    let mut k = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+    for q in 1..n {
+        let mut s = intersect(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersect(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+
+    k = 0;
+    for (q, dq) in d.iter_mut().enumerate() {
+        while z[k + 1] < pos(q) {
+            k += 1;
+        }
+        *dq = (pos(q) - pos(v[k])).powi(2) + f[v[k]];
+    }
+    d
+}
+
+impl OccupancyMap {
+    // Transformada de distância dos pixels (pixels pretos são obstáculos)
+    pub fn distance_field(&self) -> DistanceField {
+        let (width, height) = self.image.dimensions();
+        let occupied: Vec<bool> = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| is_black(self.image.get_pixel(x, y)))
+            .collect();
+        DistanceField::from_occupancy(vec![width as usize, height as usize], vec![1.0, 1.0], &occupied)
+    }
+}
+
+impl OccupancyMap3D {
+    // Transformada de distância dos voxels; as camadas ficam a dz uma da outra
+    pub fn distance_field(&self) -> DistanceField {
+        let occupied: Vec<bool> = self
+            .layers
+            .iter()
+            .flat_map(|layer| layer.pixels().map(is_black))
+            .collect();
+        DistanceField::from_occupancy(
+            vec![self.width as usize, self.height as usize, self.depth],
+            vec![1.0, 1.0, self.dz],
+            &occupied,
+        )
+    }
+}
+
+// Amostragem no eixo medial (MAPRM): cada amostra livre é empurrada na direção oposta
+// ao obstáculo mais próximo enquanto a folga aumenta, parando no eixo medial do espaço
+// livre. Roadmaps com essas amostras passam pelo meio dos corredores.
+#[derive(Clone, Debug)]
+pub struct MedialAxisSampler {
+    field: DistanceField,
+}
+
+impl MedialAxisSampler {
+    pub fn new(field: DistanceField) -> Self {
+        MedialAxisSampler { field }
+    }
+
+    // Retração de um ponto: passos do tamanho da célula ao longo do gradiente, reavaliado
+    // a cada passo para seguir a folga quando o obstáculo mais próximo muda
+    pub fn retract(&self, coords: &[f64]) -> Vec<f64> {
+        let step = self.field.spacing.iter().copied().fold(f64::INFINITY, f64::min);
+
+        // Um passo pode cair na mesma célula, então a folga igual é tolerada por no máximo
+        // uma célula; depois disso (ou ao sair da grade, ou com gradiente nulo) volta ao
+        // último ponto em que a folga aumentou
+        let max_steps: usize = self.field.dims.iter().sum();
+        let widest = self.field.spacing.iter().copied().fold(0.0, f64::max);
+        let max_flat = (widest / step).ceil() as usize;
+        let mut current = coords.to_vec();
+        let mut best = current.clone();
+        let mut best_clearance = self.field.clearance(&current);
+        let mut flat = 0;
+        for _ in 0..max_steps {
+            let grad = self.field.gradient(&current);
+            let norm = grad.iter().map(|g| g * g).sum::<f64>().sqrt();
+            if norm == 0.0 {
+                break;
+            }
+            let next: Vec<f64> = current.iter().zip(&grad).map(|(c, g)| c + g / norm * step).collect();
+            if !self.field.in_grid(&next) {
+                break;
+            }
+            let next_clearance = self.field.clearance(&next);
+            if next_clearance > best_clearance {
+                best = next.clone();
+                best_clearance = next_clearance;
+                flat = 0;
+            } else if next_clearance == best_clearance && flat < max_flat {
+                flat += 1;
+            } else {
+                break;
+            }
+            current = next;
+        }
+        best
+    }
+}
+
+impl<M: ConfigurationMap> Sampler<M> for MedialAxisSampler {
+    fn sample(&mut self, map: &M, space: &M::Space, rng: &mut dyn RngCore) -> M::State {
+        assert_eq!(M::State::DIM, self.field.dims.len(), "Dimensão do campo de distância diferente da do espaço");
+        for _ in 0..MAX_ATTEMPTS {
+            let q = space.sample_uniform(rng);
+            if !map.is_free(&q) {
+                continue;
+            }
+            let retracted = M::State::from_coords(&self.retract(&q.coords()));
+            if map.is_free(&retracted) {
+                return retracted;
+            }
+        }
+        space.sample_uniform(rng)
+    }
+}
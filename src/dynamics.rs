@@ -0,0 +1,174 @@
+use std::marker::PhantomData;
+
+use crate::se2::*;
+use crate::space::*;
+use crate::structs::Point;
+use crate::structs3d::Point3;
+
+// Modelo dinâmico x' = f(x, u) para o planejamento cinodinâmico. O estado dinâmico
+// (ex.: posição e velocidade) é projetado na configuração verificada no mapa.
+pub trait Dynamics {
+    type State: State;
+    type Control: State;
+    // Configuração vista pelo mapa (Point, Point3, ...)
+    type Config: State;
+    type Space: StateSpace<State = Self::State>;
+
+    // Espaço dos estados dinâmicos (métrica do vizinho mais próximo e amostragem),
+    // com a parte de configuração limitada pelos cantos do mapa
+    fn space(&self, lower: &Self::Config, upper: &Self::Config) -> Self::Space;
+    fn configuration(&self, state: &Self::State) -> Self::Config;
+    // Cantos da caixa de controles admissíveis
+    fn control_bounds(&self) -> (Self::Control, Self::Control);
+    fn derivative(&self, state: &Self::State, control: &Self::Control) -> Vec<f64>;
+
+    // Integra um passo de dt com o controle constante. Padrão: Runge-Kutta de 4ª ordem.
+    fn propagate(&self, state: &Self::State, control: &Self::Control, dt: f64) -> Self::State {
+        let x = state.coords();
+        let shifted = |k: &[f64], h: f64| {
+            let coords: Vec<f64> = x.iter().zip(k).map(|(xi, ki)| xi + h * ki).collect();
+            Self::State::from_coords(&coords)
+        };
+        let k1 = self.derivative(state, control);
+        let k2 = self.derivative(&shifted(&k1, dt / 2.0), control);
+        let k3 = self.derivative(&shifted(&k2, dt / 2.0), control);
+        let k4 = self.derivative(&shifted(&k3, dt), control);
+        let k: Vec<f64> = (0..x.len()).map(|i| (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]) / 6.0).collect();
+        shifted(&k, dt)
+    }
+
+    // Restrições do próprio estado além da colisão (ex.: velocidade máxima)
+    fn is_valid(&self, _state: &Self::State) -> bool {
+        true
+    }
+}
+
+// Estado de fase: posição e velocidade, com coordenadas [posição..., velocidade...]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhaseState<C: State> {
+    pub position: C,
+    pub velocity: C,
+}
+
+impl<C: State> State for PhaseState<C> {
+    const DIM: usize = 2 * C::DIM;
+
+    fn coord(&self, axis: usize) -> f64 {
+        if axis < C::DIM { self.position.coord(axis) } else { self.velocity.coord(axis - C::DIM) }
+    }
+
+    fn from_coords(coords: &[f64]) -> Self {
+        PhaseState { position: C::from_coords(&coords[..C::DIM]), velocity: C::from_coords(&coords[C::DIM..]) }
+    }
+
+    // x, y, vx, vy (e z, vz em 3D)
+    fn axis_name(axis: usize) -> String {
+        if axis < C::DIM { C::axis_name(axis) } else { format!("v{}", C::axis_name(axis - C::DIM)) }
+    }
+}
+
+// Integrador duplo: o controle é a aceleração em cada eixo, limitada por max_accel,
+// e a velocidade fica limitada por max_speed em cada eixo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DoubleIntegrator<C: State> {
+    pub max_speed: f64,
+    pub max_accel: f64,
+    config: PhantomData<C>,
+}
+
+pub type DoubleIntegrator2D = DoubleIntegrator<Point>;
+pub type DoubleIntegrator3D = DoubleIntegrator<Point3>;
+
+impl<C: State> DoubleIntegrator<C> {
+    pub fn new(max_speed: f64, max_accel: f64) -> Self {
+        DoubleIntegrator { max_speed, max_accel, config: PhantomData }
+    }
+}
+
+impl<C: State> Dynamics for DoubleIntegrator<C> {
+    type State = PhaseState<C>;
+    type Control = C;
+    type Config = C;
+    type Space = EuclideanSpace<PhaseState<C>>;
+
+    fn space(&self, lower: &C, upper: &C) -> Self::Space {
+        let speed = |v: f64| C::from_coords(&vec![v; C::DIM]);
+        EuclideanSpace::new(
+            PhaseState { position: *lower, velocity: speed(-self.max_speed) },
+            PhaseState { position: *upper, velocity: speed(self.max_speed) },
+        )
+    }
+
+    fn configuration(&self, state: &PhaseState<C>) -> C {
+        state.position
+    }
+
+    fn control_bounds(&self) -> (C, C) {
+        (C::from_coords(&vec![-self.max_accel; C::DIM]), C::from_coords(&vec![self.max_accel; C::DIM]))
+    }
+
+    fn derivative(&self, state: &PhaseState<C>, control: &C) -> Vec<f64> {
+        state.velocity.coords().into_iter().chain(control.coords()).collect()
+    }
+
+    fn is_valid(&self, state: &PhaseState<C>) -> bool {
+        (0..C::DIM).all(|axis| state.velocity.coord(axis).abs() <= self.max_speed)
+    }
+}
+
+// Uniciclo: o controle é [velocidade linear, velocidade angular]. A integração é exata
+// (arco de círculo com os controles constantes).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unicycle {
+    pub min_speed: f64, // Negativa permite ré
+    pub max_speed: f64,
+    pub max_turn_rate: f64,
+    pub rotation_weight: f64, // Peso da rotação na métrica SE(2)
+}
+
+impl Unicycle {
+    // Peso da rotação padrão: o raio de giro na velocidade máxima
+    pub fn new(min_speed: f64, max_speed: f64, max_turn_rate: f64) -> Self {
+        Unicycle { min_speed, max_speed, max_turn_rate, rotation_weight: max_speed / max_turn_rate }
+    }
+
+    pub fn with_rotation_weight(mut self, weight: f64) -> Self {
+        self.rotation_weight = weight;
+        self
+    }
+}
+
+impl Dynamics for Unicycle {
+    type State = Pose2;
+    type Control = [f64; 2];
+    type Config = Point;
+    type Space = SE2Space;
+
+    fn space(&self, lower: &Point, upper: &Point) -> SE2Space {
+        SE2Space::new(*lower, *upper, self.rotation_weight)
+    }
+
+    fn configuration(&self, state: &Pose2) -> Point {
+        Point { x: state.x, y: state.y }
+    }
+
+    fn control_bounds(&self) -> ([f64; 2], [f64; 2]) {
+        ([self.min_speed, -self.max_turn_rate], [self.max_speed, self.max_turn_rate])
+    }
+
+    fn derivative(&self, state: &Pose2, control: &[f64; 2]) -> Vec<f64> {
+        vec![control[0] * state.theta.cos(), control[0] * state.theta.sin(), control[1]]
+    }
+
+    fn propagate(&self, state: &Pose2, control: &[f64; 2], dt: f64) -> Pose2 {
+        let [v, w] = *control;
+        let theta = state.theta + w * dt;
+        let (x, y) = if w.abs() < 1e-9 {
+            (state.x + v * dt * state.theta.cos(), state.y + v * dt * state.theta.sin())
+        } else {
+            let r = v / w;
+            (state.x + r * (theta.sin() - state.theta.sin()), state.y - r * (theta.cos() - state.theta.cos()))
+        };
+        Pose2 { x, y, theta: wrap_angle(theta) }
+    }
+}
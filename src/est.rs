@@ -0,0 +1,158 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::sampler::*;
+use crate::space::*;
+use crate::tree::*;
+
+// EST (Expansive Space Trees): escolhe o nó a expandir com peso 1 / (1 + vizinhos)²,
+// favorecendo regiões pouco exploradas da árvore em vez de amostras uniformes
+pub struct ESTPlanner<M: ConfigurationMap> {
+    start: M::State,
+    goal: M::State,
+    map: M,
+    space: M::Space,
+    sampler: Box<dyn Sampler<M>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    step_size: f64,
+    goal_radius: f64,
+    goal_bias: f64,
+    density_radius: f64,
+    max_iter: u32,
+    num_collision_check_steps: i32,
+
+    tree: Tree<M::State>,
+    neighbor_count: Vec<usize>, // Vizinhos de cada nó dentro de density_radius
+    path_found: Option<Vec<M::State>>,
+}
+
+impl<M: ConfigurationMap> ESTPlanner<M> {
+    pub fn new(start: M::State, goal: M::State, map: M, step_size: f64, goal_radius: f64, num_collision_check_steps: i32) -> Self {
+        let space = map.space();
+        Self {
+            start,
+            goal,
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            step_size,
+            goal_radius,
+            goal_bias: 0.05,
+            density_radius: 2.0 * step_size,
+            max_iter: 20_000,
+            num_collision_check_steps: num_collision_check_steps.max(1),
+            tree: Tree::new(start),
+            neighbor_count: vec![0],
+            path_found: None,
+        }
+    }
+
+    pub fn with_goal_bias(mut self, bias: f64) -> Self {
+        self.goal_bias = bias.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_iter(mut self, iters: u32) -> Self {
+        self.max_iter = iters;
+        self
+    }
+
+    // Troca a fonte das amostras que dão a direção de expansão (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<M> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    // Raio usado para contar os vizinhos de cada nó (padrão: 2 * step_size)
+    pub fn with_density_radius(mut self, radius: f64) -> Self {
+        self.density_radius = radius;
+        self
+    }
+
+    // Sorteio proporcional a 1 / (1 + vizinhos)², que concentra a expansão nas bordas da árvore
+    fn select_node(&mut self) -> usize {
+        let weights: Vec<f64> = self.neighbor_count.iter().map(|&c| 1.0 / (1.0 + c as f64).powi(2)).collect();
+        let mut r = self.rng.gen_range(0.0..weights.iter().sum::<f64>());
+        for (i, w) in weights.iter().enumerate() {
+            if r < *w {
+                return i;
+            }
+            r -= w;
+        }
+        self.tree.len() - 1
+    }
+
+    fn add_node(&mut self, q: M::State, parent: usize) -> usize {
+        let near = self.tree.near(&self.space, &q, self.density_radius);
+        for &i in &near {
+            self.neighbor_count[i] += 1;
+        }
+        self.neighbor_count.push(near.len());
+        self.tree.add_node(&self.space, q, parent)
+    }
+
+    pub fn plan(&mut self) -> Option<&Vec<M::State>> {
+        for _ in 0..self.max_iter {
+            let idx = self.select_node();
+            let q = self.tree.nodes[idx].coord;
+            // Passo de step_size na direção de uma amostra (ou do objetivo)
+            let target = goal_biased(self.sampler.as_mut(), &self.map, &self.space, &self.goal, self.goal_bias, &mut self.rng);
+            let q_new = self.space.steer(&q, &target, self.step_size);
+
+            if !self.map.is_motion_free(&q, &q_new, self.num_collision_check_steps) {
+                continue;
+            }
+            let new_idx = self.add_node(q_new, idx);
+
+            if self.space.distance(&q_new, &self.goal) <= self.goal_radius
+                && self.map.is_motion_free(&q_new, &self.goal, self.num_collision_check_steps)
+            {
+                let mut path = self.tree.reconstruct_path(new_idx);
+                if path.last() != Some(&self.goal) {
+                    path.push(self.goal);
+                }
+                self.path_found = Some(path);
+                return self.path_found.as_ref();
+            }
+        }
+        None
+    }
+
+    pub fn path_cost(&self) -> Option<f64> {
+        self.path_found.as_ref().map(|path| path_length(&self.space, path))
+    }
+
+    pub fn tree(&self) -> &Tree<M::State> {
+        &self.tree
+    }
+
+    // Salva os nós da árvore no esquema comum dos planejadores
+    pub fn save_nodes_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        write_nodes_csv(filename, &[&self.tree])
+    }
+
+    // Salva o caminho final em um arquivo CSV
+    pub fn save_path_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        match &self.path_found {
+            Some(path) => write_path_csv(filename, path),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Nenhum caminho final para salvar.")),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.tree = Tree::new(self.start);
+        self.neighbor_count = vec![0];
+        self.path_found = None;
+    }
+}
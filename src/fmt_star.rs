@@ -0,0 +1,194 @@
+use std::collections::BinaryHeap;
+
+use crate::kdtree::KdTree;
+use crate::prm::HeapEntry;
+use rand::rngs::StdRng;
+
+use crate::sampler::*;
+use crate::space::*;
+use crate::tree::{path_length, trace_to_root};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum NodeSet {
+    Unvisited,
+    Open,
+    Closed,
+}
+
+// FMT* (Fast Marching Tree): sorteia um lote fixo de amostras e expande a árvore
+// por programação dinâmica "preguiçosa", com uma verificação de colisão por aresta aceita
+pub struct FMTPlanner<M: ConfigurationMap> {
+    map: M,
+    space: M::Space,
+    sampler: Box<dyn Sampler<M>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    num_samples: usize,
+    radius_multiplier: f64,
+    num_collision_check_steps: i32,
+
+    pub nodes: Vec<M::State>,
+    pub parent: Vec<Option<usize>>,
+    pub cost: Vec<f64>,
+    index: KdTree<M::State>,
+    neighbors: Vec<Option<Vec<(usize, f64)>>>, // Vizinhanças calculadas sob demanda
+    path_found: Option<Vec<M::State>>,
+    collision_checks: usize,
+}
+
+impl<M: ConfigurationMap> FMTPlanner<M> {
+    pub fn new(map: M, num_samples: usize, num_collision_check_steps: i32) -> Self {
+        let space = map.space();
+        Self {
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            num_samples,
+            radius_multiplier: 1.1,
+            num_collision_check_steps: num_collision_check_steps.max(1),
+            nodes: Vec::new(),
+            parent: Vec::new(),
+            cost: Vec::new(),
+            index: KdTree::new(),
+            neighbors: Vec::new(),
+            path_found: None,
+            collision_checks: 0,
+        }
+    }
+
+    // Troca a fonte das amostras do lote (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<M> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    // Fator (1 + eta) sobre o raio mínimo teórico do FMT*
+    pub fn with_radius_multiplier(mut self, multiplier: f64) -> Self {
+        self.radius_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    // r_n = (1 + eta) * 2 (1/d)^(1/d) (volume / volume da bola unitária)^(1/d) (ln n / n)^(1/d)
+    pub fn radius(&self) -> f64 {
+        let d = M::State::DIM as f64;
+        let n = self.nodes.len().max(2) as f64;
+        self.radius_multiplier
+            * 2.0
+            * (1.0 / d).powf(1.0 / d)
+            * (self.space.volume() / unit_ball_volume(M::State::DIM)).powf(1.0 / d)
+            * (n.ln() / n).powf(1.0 / d)
+    }
+
+    fn near(&mut self, idx: usize, radius: f64) -> Vec<(usize, f64)> {
+        if self.neighbors[idx].is_none() {
+            let q = self.nodes[idx];
+            let mut near = self.index.within_radius(&self.space, &q, radius);
+            near.retain(|&(i, _)| i != idx);
+            self.neighbors[idx] = Some(near);
+        }
+        self.neighbors[idx].clone().unwrap()
+    }
+
+    // Amostra o lote de estados livres; início e objetivo ocupam os índices 0 e 1
+    fn sample_batch(&mut self, start: M::State, goal: M::State) {
+        self.nodes = vec![start, goal];
+        self.rng = planner_rng(self.seed);
+        // Como no PRM: no máximo MAX_ATTEMPTS tentativas por amostra, e o lote fica com o que houver
+        for _ in 0..self.num_samples * MAX_ATTEMPTS {
+            if self.nodes.len() >= self.num_samples + 2 {
+                break;
+            }
+            let q = self.sampler.sample(&self.map, &self.space, &mut self.rng);
+            if self.map.is_free(&q) {
+                self.nodes.push(q);
+            }
+        }
+        let n = self.nodes.len();
+        self.index = KdTree::from_points(self.nodes.clone());
+        self.parent = vec![None; n];
+        self.cost = vec![f64::INFINITY; n];
+        self.neighbors = vec![None; n];
+        self.collision_checks = 0;
+    }
+
+    pub fn plan(&mut self, start: M::State, goal: M::State) -> Option<&Vec<M::State>> {
+        self.path_found = None;
+        if !self.map.is_free(&start) || !self.map.is_free(&goal) {
+            return None;
+        }
+
+        self.sample_batch(start, goal);
+        let (start_idx, goal_idx) = (0, 1);
+        let radius = self.radius();
+
+        let mut set = vec![NodeSet::Unvisited; self.nodes.len()];
+        let mut open = BinaryHeap::new();
+        set[start_idx] = NodeSet::Open;
+        self.cost[start_idx] = 0.0;
+        open.push(HeapEntry { cost: 0.0, idx: start_idx });
+
+        while let Some(HeapEntry { idx: z, .. }) = open.pop() {
+            if z == goal_idx {
+                break;
+            }
+
+            let mut new_open = Vec::new();
+            for (x, _) in self.near(z, radius) {
+                if set[x] != NodeSet::Unvisited {
+                    continue;
+                }
+                // Melhor pai entre os vizinhos abertos de x, sem olhar colisão
+                let best = self
+                    .near(x, radius)
+                    .into_iter()
+                    .filter(|&(y, _)| set[y] == NodeSet::Open)
+                    .map(|(y, d)| (y, self.cost[y] + d))
+                    .min_by(|a, b| a.1.total_cmp(&b.1));
+
+                if let Some((y_min, c)) = best {
+                    self.collision_checks += 1;
+                    if self.map.is_motion_free(&self.nodes[y_min], &self.nodes[x], self.num_collision_check_steps) {
+                        self.parent[x] = Some(y_min);
+                        self.cost[x] = c;
+                        new_open.push(x);
+                    }
+                }
+            }
+
+            // Os novos nós só viram "abertos" depois de processar z
+            for x in new_open {
+                set[x] = NodeSet::Open;
+                open.push(HeapEntry { cost: self.cost[x], idx: x });
+            }
+            set[z] = NodeSet::Closed;
+        }
+
+        self.parent[goal_idx]?;
+
+        let path = trace_to_root(goal_idx, |i| self.parent[i]).into_iter().map(|i| self.nodes[i]).collect();
+        self.path_found = Some(path);
+        self.path_found.as_ref()
+    }
+
+    pub fn path_cost(&self) -> Option<f64> {
+        self.path_found.as_ref().map(|path| path_length(&self.space, path))
+    }
+
+    // Verificações de colisão de arestas feitas no último plan()
+    pub fn collision_checks(&self) -> usize {
+        self.collision_checks
+    }
+
+    pub fn map(&self) -> &M {
+        &self.map
+    }
+}
@@ -0,0 +1,63 @@
+use crate::readmap::OccupancyMap;
+use crate::readmap3d::OccupancyMap3D;
+
+// Raio do robô (disco em 2D, esfera em 3D), em pixels ou em metros com a resolução do mapa
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RobotRadius {
+    Pixels(f64),
+    Meters { radius: f64, meters_per_pixel: f64 },
+}
+
+impl RobotRadius {
+    pub fn in_pixels(&self) -> f64 {
+        match *self {
+            RobotRadius::Pixels(r) => r,
+            RobotRadius::Meters { radius, meters_per_pixel } => radius / meters_per_pixel,
+        }
+    }
+}
+
+impl OccupancyMap {
+    // Espaço de configuração de um robô em disco: todo pixel a até `radius` de um
+    // obstáculo vira obstáculo, então o planejamento continua tratando o robô como
+    // ponto. A imagem original fica em original_image para visualização.
+    pub fn inflate(&self, radius: RobotRadius) -> OccupancyMap {
+        let r = radius.in_pixels();
+        let field = self.distance_field();
+        let mut image = self.image.clone();
+        for (pixel, &d) in image.pixels_mut().zip(field.values()) {
+            if d <= r {
+                *pixel = image::Rgb([0, 0, 0]);
+            }
+        }
+        OccupancyMap {
+            image,
+            pixel_bounds: self.pixel_bounds,
+            original_image: Some(self.display_image().clone()),
+        }
+    }
+}
+
+impl OccupancyMap3D {
+    // Mesmo que OccupancyMap::inflate para um robô esférico. As distâncias em z usam dz,
+    // então o raio está nas unidades do estado (pixels em x/y).
+    pub fn inflate(&self, radius: RobotRadius) -> OccupancyMap3D {
+        let r = radius.in_pixels();
+        let field = self.distance_field();
+        let mut layers = self.layers.clone();
+        let pixels = layers.iter_mut().flat_map(|layer| layer.pixels_mut());
+        for (pixel, &d) in pixels.zip(field.values()) {
+            if d <= r {
+                *pixel = image::Rgb([0, 0, 0]);
+            }
+        }
+        OccupancyMap3D {
+            layers,
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+            dz: self.dz,
+            original_layers: Some(self.display_layers().to_vec()),
+        }
+    }
+}
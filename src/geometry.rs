@@ -0,0 +1,357 @@
+use crate::persist::Fingerprint;
+use crate::space::*;
+use crate::structs3d::Point3;
+
+// Mundo de obstáculos analíticos em 3D (caixas alinhadas e orientadas, cilindros,
+// esferas e cápsulas), alternativa exata aos mapas raster. Todos os primitivos são
+// convexos e fechados: a borda conta como obstáculo.
+
+fn add(a: &Point3, b: &Point3) -> Point3 {
+    Point3 { x: a.x + b.x, y: a.y + b.y, z: a.z + b.z }
+}
+
+fn sub(a: &Point3, b: &Point3) -> Point3 {
+    Point3 { x: a.x - b.x, y: a.y - b.y, z: a.z - b.z }
+}
+
+fn scale(a: &Point3, s: f64) -> Point3 {
+    Point3 { x: a.x * s, y: a.y * s, z: a.z * s }
+}
+
+fn dot(a: &Point3, b: &Point3) -> f64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+fn norm(a: &Point3) -> f64 {
+    dot(a, a).sqrt()
+}
+
+fn lerp(a: &Point3, b: &Point3, t: f64) -> Point3 {
+    add(a, &scale(&sub(b, a), t))
+}
+
+// Distância do ponto p ao segmento ab
+fn point_segment_distance(p: &Point3, a: &Point3, b: &Point3) -> f64 {
+    let ab = sub(b, a);
+    let len2 = dot(&ab, &ab);
+    let t = if len2 > 0.0 { (dot(&sub(p, a), &ab) / len2).clamp(0.0, 1.0) } else { 0.0 };
+    p.dist(&lerp(a, b, t))
+}
+
+// Distância entre os segmentos p1q1 e p2q2 (Ericson, Real-Time Collision Detection, 5.1.9)
+fn segment_segment_distance(p1: &Point3, q1: &Point3, p2: &Point3, q2: &Point3) -> f64 {
+    const EPS: f64 = 1e-12;
+    let (d1, d2, r) = (sub(q1, p1), sub(q2, p2), sub(p1, p2));
+    let (a, e, f) = (dot(&d1, &d1), dot(&d2, &d2), dot(&d2, &r));
+    if a <= EPS && e <= EPS {
+        return p1.dist(p2);
+    }
+    let (s, t) = if a <= EPS {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = dot(&d1, &r);
+        if e <= EPS {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = dot(&d1, &d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > EPS { ((b * f - c * e) / denom).clamp(0.0, 1.0) } else { 0.0 };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+    lerp(p1, q1, s).dist(&lerp(p2, q2, t))
+}
+
+// Mínimo de uma distância a um conjunto convexo ao longo do segmento ab. A função é
+// convexa em t, então a busca da razão áurea converge para o mínimo exato (até a
+// precisão de ponto flutuante).
+pub(crate) fn segment_minimum(a: &Point3, b: &Point3, distance: impl Fn(&Point3) -> f64) -> f64 {
+    const INV_PHI: f64 = 0.618_033_988_749_894_9;
+    let at = |t: f64| distance(&lerp(a, b, t));
+    let length = a.dist(b);
+
+    let (mut lo, mut hi) = (0.0, 1.0);
+    let mut c = hi - INV_PHI * (hi - lo);
+    let mut d = lo + INV_PHI * (hi - lo);
+    let (mut fc, mut fd) = (at(c), at(d));
+    while (hi - lo) * length > 1e-12 {
+        if fc == 0.0 || fd == 0.0 {
+            return 0.0;
+        }
+        if fc < fd {
+            hi = d;
+            (d, fd) = (c, fc);
+            c = hi - INV_PHI * (hi - lo);
+            fc = at(c);
+        } else {
+            lo = c;
+            (c, fc) = (d, fd);
+            d = lo + INV_PHI * (hi - lo);
+            fd = at(d);
+        }
+    }
+    fc.min(fd).min(at(0.0)).min(at(1.0))
+}
+
+// Intervalo de t em [0, 1] em que o segmento ab fica na caixa [-half, half] (método
+// das faixas), ou None se não a cruza
+fn slab_interval(a: &Point3, b: &Point3, half: &Point3) -> Option<(f64, f64)> {
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+    for axis in 0..3 {
+        let (start, h) = (a.coord(axis), half.coord(axis));
+        let delta = b.coord(axis) - start;
+        if delta.abs() < 1e-15 {
+            if start.abs() > h {
+                return None;
+            }
+            continue;
+        }
+        let (mut near, mut far) = ((-h - start) / delta, (h - start) / delta);
+        if near > far {
+            std::mem::swap(&mut near, &mut far);
+        }
+        t0 = t0.max(near);
+        t1 = t1.min(far);
+        if t0 > t1 {
+            return None;
+        }
+    }
+    Some((t0, t1))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Primitive {
+    // Caixa alinhada aos eixos entre os cantos min e max
+    Aabb { min: Point3, max: Point3 },
+    // Caixa orientada: centro, meias-dimensões e eixos locais (base ortonormal)
+    Obb { center: Point3, half_extents: Point3, axes: [Point3; 3] },
+    // Cilindro sólido de eixo base -> top
+    Cylinder { base: Point3, top: Point3, radius: f64 },
+    Sphere { center: Point3, radius: f64 },
+    // Segmento a -> b engrossado por radius
+    Capsule { a: Point3, b: Point3, radius: f64 },
+}
+
+impl Primitive {
+    // Caixa orientada com rotação roll (x), pitch (y) e yaw (z), aplicadas nessa ordem
+    pub fn oriented_box(center: Point3, size: Point3, roll: f64, pitch: f64, yaw: f64) -> Self {
+        let (sr, cr) = roll.sin_cos();
+        let (sp, cp) = pitch.sin_cos();
+        let (sy, cy) = yaw.sin_cos();
+        // Colunas de Rz(yaw) * Ry(pitch) * Rx(roll)
+        let axes = [
+            Point3 { x: cy * cp, y: sy * cp, z: -sp },
+            Point3 { x: cy * sp * sr - sy * cr, y: sy * sp * sr + cy * cr, z: cp * sr },
+            Point3 { x: cy * sp * cr + sy * sr, y: sy * sp * cr - cy * sr, z: cp * cr },
+        ];
+        Primitive::Obb { center, half_extents: scale(&size, 0.5), axes }
+    }
+
+    // Cilindro vertical apoiado no chão (o "cylinder" do OBSTACLES_LIST)
+    pub fn vertical_cylinder(x: f64, y: f64, radius: f64, height: f64) -> Self {
+        Primitive::Cylinder { base: Point3 { x, y, z: 0.0 }, top: Point3 { x, y, z: height }, radius }
+    }
+
+    // Caixa apoiada no chão e centrada em (x, y) no plano (o "box" do OBSTACLES_LIST)
+    pub fn floor_box(x: f64, y: f64, size_x: f64, size_y: f64, size_z: f64) -> Self {
+        Primitive::Aabb {
+            min: Point3 { x: x - size_x / 2.0, y: y - size_y / 2.0, z: 0.0 },
+            max: Point3 { x: x + size_x / 2.0, y: y + size_y / 2.0, z: size_z },
+        }
+    }
+
+    // Ponto nas coordenadas locais da caixa orientada
+    fn to_local(p: &Point3, center: &Point3, axes: &[Point3; 3]) -> Point3 {
+        let r = sub(p, center);
+        Point3 { x: dot(&r, &axes[0]), y: dot(&r, &axes[1]), z: dot(&r, &axes[2]) }
+    }
+
+    pub fn contains(&self, p: &Point3) -> bool {
+        self.distance(p) == 0.0
+    }
+
+    // Distância do ponto ao sólido (0 dentro ou na borda)
+    pub fn distance(&self, p: &Point3) -> f64 {
+        let box_distance = |local: &Point3, half: &Point3| {
+            let excess = |axis: usize| (local.coord(axis).abs() - half.coord(axis)).max(0.0);
+            norm(&Point3 { x: excess(0), y: excess(1), z: excess(2) })
+        };
+        match self {
+            Primitive::Aabb { min, max } => {
+                let center = scale(&add(min, max), 0.5);
+                box_distance(&sub(p, &center), &scale(&sub(max, min), 0.5))
+            }
+            Primitive::Obb { center, half_extents, axes } => box_distance(&Self::to_local(p, center, axes), half_extents),
+            Primitive::Cylinder { base, top, radius } => {
+                let axis = sub(top, base);
+                // Com base e topo iguais o cilindro degenera em bola de raio radius, sem dividir por zero
+                let length = norm(&axis).max(1e-12);
+                let along = dot(&sub(p, base), &axis) / length;
+                let radial = norm(&sub(&sub(p, base), &scale(&axis, along / length)));
+                let dr = (radial - radius).max(0.0);
+                let dh = (-along).max(along - length).max(0.0);
+                dr.hypot(dh)
+            }
+            Primitive::Sphere { center, radius } => (p.dist(center) - radius).max(0.0),
+            Primitive::Capsule { a, b, radius } => (point_segment_distance(p, a, b) - radius).max(0.0),
+        }
+    }
+
+    // Distância do segmento ab ao sólido (0 se o cruza)
+    pub fn segment_distance(&self, a: &Point3, b: &Point3) -> f64 {
+        match self {
+            Primitive::Sphere { center, radius } => (point_segment_distance(center, a, b) - radius).max(0.0),
+            Primitive::Capsule { a: c, b: d, radius } => (segment_segment_distance(a, b, c, d) - radius).max(0.0),
+            Primitive::Aabb { .. } | Primitive::Obb { .. } if self.segment_intersects(a, b) => 0.0,
+            _ => segment_minimum(a, b, |p| self.distance(p)),
+        }
+    }
+
+    pub fn segment_intersects(&self, a: &Point3, b: &Point3) -> bool {
+        match self {
+            Primitive::Aabb { min, max } => {
+                let center = scale(&add(min, max), 0.5);
+                slab_interval(&sub(a, &center), &sub(b, &center), &scale(&sub(max, min), 0.5)).is_some()
+            }
+            Primitive::Obb { center, half_extents, axes } => {
+                slab_interval(&Self::to_local(a, center, axes), &Self::to_local(b, center, axes), half_extents).is_some()
+            }
+            _ => self.segment_distance(a, b) == 0.0,
+        }
+    }
+
+    // Tipo e parâmetros, para a impressão digital do mapa
+    pub fn write_fingerprint(&self, hasher: &mut Fingerprint) {
+        let (tag, points, radius): (&[u8], Vec<&Point3>, f64) = match self {
+            Primitive::Aabb { min, max } => (b"aabb", vec![min, max], 0.0),
+            Primitive::Obb { center, half_extents, axes } => {
+                (b"obb", vec![center, half_extents, &axes[0], &axes[1], &axes[2]], 0.0)
+            }
+            Primitive::Cylinder { base, top, radius } => (b"cylinder", vec![base, top], *radius),
+            Primitive::Sphere { center, radius } => (b"sphere", vec![center], *radius),
+            Primitive::Capsule { a, b, radius } => (b"capsule", vec![a, b], *radius),
+        };
+        hasher.write(tag);
+        for p in points {
+            for v in [p.x, p.y, p.z] {
+                hasher.write(&v.to_le_bytes());
+            }
+        }
+        hasher.write(&radius.to_le_bytes());
+    }
+
+    // Caixa alinhada que envolve o primitivo
+    pub fn bounding_box(&self) -> (Point3, Point3) {
+        let grow = |lo: Point3, hi: Point3, r: f64| (sub(&lo, &Point3 { x: r, y: r, z: r }), add(&hi, &Point3 { x: r, y: r, z: r }));
+        let span = |a: &Point3, b: &Point3| {
+            (Point3 { x: a.x.min(b.x), y: a.y.min(b.y), z: a.z.min(b.z) }, Point3 { x: a.x.max(b.x), y: a.y.max(b.y), z: a.z.max(b.z) })
+        };
+        match self {
+            Primitive::Aabb { min, max } => (*min, *max),
+            Primitive::Obb { center, half_extents, axes } => {
+                let extent = |axis: usize| (0..3).map(|i| (axes[i].coord(axis) * half_extents.coord(i)).abs()).sum::<f64>();
+                let e = Point3 { x: extent(0), y: extent(1), z: extent(2) };
+                (sub(center, &e), add(center, &e))
+            }
+            Primitive::Cylinder { base, top, radius } => {
+                let (lo, hi) = span(base, top);
+                grow(lo, hi, *radius)
+            }
+            Primitive::Sphere { center, radius } => grow(*center, *center, *radius),
+            Primitive::Capsule { a, b, radius } => {
+                let (lo, hi) = span(a, b);
+                grow(lo, hi, *radius)
+            }
+        }
+    }
+}
+
+// Mapa 3D de primitivos dentro da caixa [lower, upper]. Um ponto é livre se está na
+// caixa e a mais de `clearance` de todo obstáculo (robô esférico de raio clearance);
+// o movimento em linha reta é verificado de forma exata, sem amostragem.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrimitiveMap {
+    pub lower: Point3,
+    pub upper: Point3,
+    pub obstacles: Vec<Primitive>,
+    pub clearance: f64,
+}
+
+impl PrimitiveMap {
+    pub fn new(lower: Point3, upper: Point3) -> Self {
+        PrimitiveMap { lower, upper, obstacles: Vec::new(), clearance: 0.0 }
+    }
+
+    pub fn with_obstacle(mut self, obstacle: Primitive) -> Self {
+        self.obstacles.push(obstacle);
+        self
+    }
+
+    pub fn with_obstacles(mut self, obstacles: impl IntoIterator<Item = Primitive>) -> Self {
+        self.obstacles.extend(obstacles);
+        self
+    }
+
+    pub fn with_clearance(mut self, clearance: f64) -> Self {
+        self.clearance = clearance.max(0.0);
+        self
+    }
+
+    fn in_bounds(&self, p: &Point3) -> bool {
+        (0..3).all(|axis| p.coord(axis) >= self.lower.coord(axis) && p.coord(axis) <= self.upper.coord(axis))
+    }
+
+    // Distância ao obstáculo mais próximo (infinita sem obstáculos)
+    pub fn distance(&self, p: &Point3) -> f64 {
+        self.obstacles.iter().map(|o| o.distance(p)).fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn segment_distance(&self, a: &Point3, b: &Point3) -> f64 {
+        self.obstacles.iter().map(|o| o.segment_distance(a, b)).fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn is_point_inside(&self, p: &Point3) -> bool {
+        self.obstacles.iter().any(|o| o.contains(p))
+    }
+
+    pub fn segment_intersects(&self, a: &Point3, b: &Point3) -> bool {
+        self.obstacles.iter().any(|o| o.segment_intersects(a, b))
+    }
+}
+
+impl ConfigurationMap for PrimitiveMap {
+    type State = Point3;
+    type Space = EuclideanSpace<Point3>;
+
+    fn space(&self) -> EuclideanSpace<Point3> {
+        EuclideanSpace::new(self.lower, self.upper)
+    }
+
+    fn is_free(&self, state: &Point3) -> bool {
+        self.in_bounds(state) && self.obstacles.iter().all(|o| o.distance(state) > self.clearance)
+    }
+
+    // Exato: num_steps é ignorado. A caixa é convexa, então basta as pontas estarem nela.
+    fn is_motion_free(&self, a: &Point3, b: &Point3, _num_steps: i32) -> bool {
+        self.in_bounds(a) && self.in_bounds(b) && self.obstacles.iter().all(|o| o.segment_distance(a, b) > self.clearance)
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = Fingerprint::new();
+        for v in [self.lower.x, self.lower.y, self.lower.z, self.upper.x, self.upper.y, self.upper.z, self.clearance] {
+            hasher.write(&v.to_le_bytes());
+        }
+        for obstacle in &self.obstacles {
+            obstacle.write_fingerprint(&mut hasher);
+        }
+        hasher.finish()
+    }
+}
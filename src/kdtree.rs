@@ -0,0 +1,244 @@
+use crate::space::*;
+
+#[derive(Clone, Debug)]
+struct KdNode {
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+    axis: usize, // Eixo de corte: à esquerda coord <= valor do nó, à direita coord >= valor
+}
+
+// Índice espacial incremental (KD-tree) para vizinho mais próximo, k vizinhos e raio.
+// Cada item é identificado pela ordem de inserção, igual ao índice do nó na árvore
+// ou no roadmap. Inserções entram como folhas; quando o número de itens dobra, a
+// árvore é reconstruída balanceada (corte na mediana do eixo de maior extensão).
+//
+// As buscas usam a distância do espaço e podam subárvores com StateSpace::axis_distance,
+// então continuam corretas em qualquer espaço (sem poda, viram uma varredura completa).
+#[derive(Clone, Debug)]
+pub struct KdTree<S> {
+    points: Vec<S>,
+    nodes: Vec<KdNode>,
+    root: Option<usize>,
+    built_len: usize,    // Itens presentes na última reconstrução
+    next_rebuild: usize, // Tamanho em que a próxima reconstrução acontece
+}
+
+impl<S: State> Default for KdTree<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: State> KdTree<S> {
+    const MIN_REBUILD: usize = 64;
+
+    pub fn new() -> Self {
+        KdTree { points: Vec::new(), nodes: Vec::new(), root: None, built_len: 0, next_rebuild: Self::MIN_REBUILD }
+    }
+
+    pub fn from_points(points: Vec<S>) -> Self {
+        let mut index = KdTree { points, ..Self::new() };
+        index.rebuild();
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn points(&self) -> &[S] {
+        &self.points
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    // Insere um ponto e retorna o seu índice
+    pub fn insert(&mut self, point: S) -> usize {
+        let idx = self.points.len();
+        self.points.push(point);
+        if self.points.len() >= self.next_rebuild {
+            self.rebuild();
+            return idx;
+        }
+
+        let mut node = KdNode { left: None, right: None, parent: None, axis: 0 };
+        let mut current = self.root;
+        while let Some(c) = current {
+            let axis = self.nodes[c].axis;
+            let go_left = point.coord(axis) < self.points[c].coord(axis);
+            let child = if go_left { self.nodes[c].left } else { self.nodes[c].right };
+            if child.is_none() {
+                if go_left {
+                    self.nodes[c].left = Some(idx);
+                } else {
+                    self.nodes[c].right = Some(idx);
+                }
+                node.parent = Some(c);
+                node.axis = (axis + 1) % S::DIM;
+            }
+            current = child;
+        }
+        if self.root.is_none() {
+            self.root = Some(idx);
+        }
+        self.nodes.push(node);
+        idx
+    }
+
+    // Remove os itens a partir de `len`. Itens inseridos depois da última reconstrução
+    // são folhas e saem em ordem inversa; nos demais casos o índice é reconstruído.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.points.len() {
+            return;
+        }
+        if len < self.built_len {
+            self.points.truncate(len);
+            self.rebuild();
+            return;
+        }
+        while self.points.len() > len {
+            let idx = self.points.len() - 1;
+            match self.nodes[idx].parent {
+                Some(p) if self.nodes[p].left == Some(idx) => self.nodes[p].left = None,
+                Some(p) => self.nodes[p].right = None,
+                None => self.root = None,
+            }
+            self.points.pop();
+            self.nodes.pop();
+        }
+    }
+
+    fn rebuild(&mut self) {
+        let n = self.points.len();
+        self.nodes = vec![KdNode { left: None, right: None, parent: None, axis: 0 }; n];
+        let mut items: Vec<usize> = (0..n).collect();
+        self.root = self.build(&mut items);
+        self.built_len = n;
+        self.next_rebuild = (2 * n).max(Self::MIN_REBUILD);
+    }
+
+    // Constrói a subárvore balanceada dos itens, iterativamente para não estourar a pilha
+    fn build(&mut self, items: &mut [usize]) -> Option<usize> {
+        let mut root = None;
+        let mut stack: Vec<(usize, usize, Option<usize>, bool)> = vec![(0, items.len(), None, false)];
+        while let Some((lo, hi, parent, is_left)) = stack.pop() {
+            if lo >= hi {
+                continue;
+            }
+            let slice = &mut items[lo..hi];
+            let axis = self.widest_axis(slice);
+            let mid = slice.len() / 2;
+            let points = &self.points;
+            slice.select_nth_unstable_by(mid, |&a, &b| points[a].coord(axis).total_cmp(&points[b].coord(axis)));
+            let node = slice[mid];
+
+            self.nodes[node] = KdNode { left: None, right: None, parent, axis };
+            match parent {
+                Some(p) if is_left => self.nodes[p].left = Some(node),
+                Some(p) => self.nodes[p].right = Some(node),
+                None => root = Some(node),
+            }
+            stack.push((lo, lo + mid, Some(node), true));
+            stack.push((lo + mid + 1, hi, Some(node), false));
+        }
+        root
+    }
+
+    fn widest_axis(&self, items: &[usize]) -> usize {
+        (0..S::DIM)
+            .map(|axis| {
+                let (min, max) = items.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &i| {
+                    let c = self.points[i].coord(axis);
+                    (min.min(c), max.max(c))
+                });
+                (axis, max - min)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map_or(0, |(axis, _)| axis)
+    }
+
+    // Percorre a árvore visitando primeiro o lado do ponto de consulta. `visit` recebe
+    // (índice, distância) e devolve o raio atual: subárvores mais distantes são podadas.
+    fn search<Sp: StateSpace<State = S>>(&self, space: &Sp, query: &S, mut visit: impl FnMut(usize, f64) -> f64) {
+        let mut radius = f64::INFINITY;
+        let mut stack: Vec<(usize, f64)> = self.root.map(|r| (r, 0.0)).into_iter().collect();
+        while let Some((idx, lower_bound)) = stack.pop() {
+            if lower_bound > radius {
+                continue;
+            }
+            let point = &self.points[idx];
+            radius = visit(idx, space.distance(point, query));
+
+            let node = &self.nodes[idx];
+            let (q, split) = (query.coord(node.axis), point.coord(node.axis));
+            let (near, far) = if q < split { (node.left, node.right) } else { (node.right, node.left) };
+            if let Some(far) = far {
+                stack.push((far, lower_bound.max(space.axis_distance(node.axis, q, split))));
+            }
+            if let Some(near) = near {
+                stack.push((near, lower_bound));
+            }
+        }
+    }
+
+    // Item mais próximo e sua distância
+    pub fn nearest<Sp: StateSpace<State = S>>(&self, space: &Sp, query: &S) -> Option<(usize, f64)> {
+        let mut best: Option<(usize, f64)> = None;
+        self.search(space, query, |idx, d| {
+            if best.is_none_or(|(_, best_d)| d < best_d) {
+                best = Some((idx, d));
+            }
+            best.map_or(f64::INFINITY, |(_, best_d)| best_d)
+        });
+        best
+    }
+
+    // Item mais próximo entre os que passam no filtro (ex.: itens ainda vivos)
+    pub fn nearest_where<Sp: StateSpace<State = S>>(&self, space: &Sp, query: &S, keep: impl Fn(usize) -> bool) -> Option<(usize, f64)> {
+        let mut best: Option<(usize, f64)> = None;
+        self.search(space, query, |idx, d| {
+            if keep(idx) && best.is_none_or(|(_, best_d)| d < best_d) {
+                best = Some((idx, d));
+            }
+            best.map_or(f64::INFINITY, |(_, best_d)| best_d)
+        });
+        best
+    }
+
+    // Os k itens mais próximos, em ordem crescente de distância
+    pub fn k_nearest<Sp: StateSpace<State = S>>(&self, space: &Sp, query: &S, k: usize) -> Vec<(usize, f64)> {
+        let mut found: Vec<(usize, f64)> = Vec::with_capacity(k + 1);
+        if k == 0 {
+            return found;
+        }
+        self.search(space, query, |idx, d| {
+            if found.len() < k || d < found[k - 1].1 {
+                let pos = found.partition_point(|&(_, fd)| fd <= d);
+                found.insert(pos, (idx, d));
+                found.truncate(k);
+            }
+            if found.len() < k { f64::INFINITY } else { found[k - 1].1 }
+        });
+        found
+    }
+
+    // Todos os itens a até `radius` do ponto, em ordem crescente de distância
+    pub fn within_radius<Sp: StateSpace<State = S>>(&self, space: &Sp, query: &S, radius: f64) -> Vec<(usize, f64)> {
+        let mut found = Vec::new();
+        self.search(space, query, |idx, d| {
+            if d <= radius {
+                found.push((idx, d));
+            }
+            radius
+        });
+        found.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        found
+    }
+}
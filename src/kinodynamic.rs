@@ -0,0 +1,307 @@
+use rand::{Rng, RngCore};
+use rand::rngs::StdRng;
+
+use crate::dynamics::*;
+use crate::sampler::planner_rng;
+use crate::space::*;
+use crate::tree::*;
+
+// Ponto de uma trajetória: instante, estado e controle aplicado a partir dele
+// (None no último ponto)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TimedState<S, U> {
+    pub time: f64,
+    pub state: S,
+    pub control: Option<U>,
+}
+
+// Nó de uma cadeia raiz -> fim: estado, instante em passos de dt e a aresta
+// (controle, passos) vinda do pai
+pub(crate) type ChainLink<S, U> = (S, usize, Option<(U, usize)>);
+
+// Parte comum dos planejadores cinodinâmicos (RRT cinodinâmico e SST): mapa, modelo
+// dinâmico e integração dos controles com verificação de colisão
+pub(crate) struct Propagator<M: ConfigurationMap, D: Dynamics<Config = M::State>> {
+    pub(crate) map: M,
+    pub(crate) map_space: M::Space,
+    pub(crate) dynamics: D,
+    pub(crate) space: D::Space,
+    pub(crate) control_space: EuclideanSpace<D::Control>,
+    pub(crate) dt: f64,
+    pub(crate) min_steps: usize,
+    pub(crate) max_steps: usize,
+    pub(crate) collision_steps: i32,
+}
+
+impl<M: ConfigurationMap, D: Dynamics<Config = M::State>> Propagator<M, D> {
+    pub(crate) fn new(map: M, dynamics: D, dt: f64) -> Self {
+        let map_space = map.space();
+        let (lower, upper) = map_space.bounds();
+        let space = dynamics.space(&lower, &upper);
+        let (u_min, u_max) = dynamics.control_bounds();
+        Propagator {
+            map,
+            map_space,
+            dynamics,
+            space,
+            control_space: EuclideanSpace::new(u_min, u_max),
+            dt,
+            min_steps: 1,
+            max_steps: 10,
+            collision_steps: 1,
+        }
+    }
+
+    // Distância entre as configurações de dois estados, na métrica do mapa
+    pub(crate) fn configuration_distance(&self, a: &D::State, b: &D::State) -> f64 {
+        self.map_space.distance(&self.dynamics.configuration(a), &self.dynamics.configuration(b))
+    }
+
+    // Estado dentro dos limites do modelo e com a configuração livre no mapa
+    pub(crate) fn is_state_free(&self, state: &D::State) -> bool {
+        self.dynamics.is_valid(state) && self.map.is_free(&self.dynamics.configuration(state))
+    }
+
+    // Integra o controle por até `steps` passos, parando antes do primeiro passo inválido.
+    // Devolve o estado final e os passos feitos (0 se nem o primeiro passo é válido).
+    pub(crate) fn propagate_checked(&self, from: &D::State, control: &D::Control, steps: usize) -> (D::State, usize) {
+        let mut state = *from;
+        for done in 0..steps {
+            let next = self.dynamics.propagate(&state, control, self.dt);
+            let (a, b) = (self.dynamics.configuration(&state), self.dynamics.configuration(&next));
+            if !self.dynamics.is_valid(&next) || !self.map.is_motion_free(&a, &b, self.collision_steps) {
+                return (state, done);
+            }
+            state = next;
+        }
+        (state, steps)
+    }
+
+    // Melhor de `samples` controles aleatórios com duração aleatória: o que termina mais
+    // perto do alvo. Devolve (estado final, controle, passos), ou None se todos colidem.
+    pub(crate) fn random_extension(
+        &self,
+        from: &D::State,
+        target: &D::State,
+        samples: usize,
+        rng: &mut dyn RngCore,
+    ) -> Option<(D::State, D::Control, usize)> {
+        let mut best: Option<(D::State, D::Control, usize, f64)> = None;
+        for _ in 0..samples {
+            let control = self.control_space.sample_uniform(rng);
+            let steps = rng.gen_range(self.min_steps..=self.max_steps);
+            let (state, done) = self.propagate_checked(from, &control, steps);
+            if done == 0 {
+                continue;
+            }
+            let d = self.space.distance(&state, target);
+            if best.is_none_or(|(_, _, _, best_d)| d < best_d) {
+                best = Some((state, control, done, d));
+            }
+        }
+        best.map(|(state, control, steps, _)| (state, control, steps))
+    }
+
+    // Trajetória passo a passo a partir da cadeia de nós da raiz até o fim. Cada aresta é
+    // reintegrada com o seu controle; a integração é determinística, então reproduz os
+    // mesmos estados.
+    pub(crate) fn trajectory(&self, chain: &[ChainLink<D::State, D::Control>]) -> Vec<TimedState<D::State, D::Control>> {
+        let mut trajectory = Vec::new();
+        for w in chain.windows(2) {
+            let (mut state, start_step, _) = w[0];
+            let (control, steps) = w[1].2.expect("Aresta sem controle");
+            for step in 0..steps {
+                let time = (start_step + step) as f64 * self.dt;
+                trajectory.push(TimedState { time, state, control: Some(control) });
+                state = self.dynamics.propagate(&state, &control, self.dt);
+            }
+        }
+        if let Some(&(state, end_step, _)) = chain.last() {
+            trajectory.push(TimedState { time: end_step as f64 * self.dt, state, control: None });
+        }
+        trajectory
+    }
+}
+
+// CSV da trajetória: t, eixos do estado e controles u0, u1, ... (NaN no último ponto)
+pub fn write_trajectory_csv<S: State, U: State>(filename: &str, trajectory: &[TimedState<S, U>]) -> std::io::Result<()> {
+    let mut w = csv::Writer::from_path(filename)?;
+    let mut header = vec!["t".to_string()];
+    header.extend((0..S::DIM).map(S::axis_name));
+    header.extend((0..U::DIM).map(|i| format!("u{}", i)));
+    w.write_record(&header)?;
+
+    for point in trajectory {
+        let mut record = vec![point.time.to_string()];
+        record.extend(point.state.coords().iter().map(|c| c.to_string()));
+        match &point.control {
+            Some(u) => record.extend(u.coords().iter().map(|c| c.to_string())),
+            None => record.extend((0..U::DIM).map(|_| "NaN".to_string())),
+        }
+        w.write_record(&record)?;
+    }
+    w.flush()?;
+    Ok(())
+}
+
+// RRT cinodinâmico: em vez de ligar estados por retas, cada expansão sorteia controles
+// e integra o modelo dinâmico a partir do nó mais próximo da amostra, ficando com a
+// trajetória que termina mais perto dela. A colisão é verificada a cada passo de
+// integração na configuração projetada no mapa. O objetivo é atingido quando a
+// configuração fica a até goal_radius da configuração do estado objetivo.
+pub struct KinodynamicPlanner<M: ConfigurationMap, D: Dynamics<Config = M::State>> {
+    start: D::State,
+    goal: D::State,
+    model: Propagator<M, D>,
+    seed: Option<u64>,
+    rng: StdRng,
+    control_samples: usize,
+    goal_radius: f64,
+    goal_bias: f64,
+    max_iter: u32,
+
+    tree: Tree<D::State>,
+    // Controle e número de passos de integração do pai até cada nó (None na raiz)
+    edges: Vec<Option<(D::Control, usize)>>,
+    steps_from_root: Vec<usize>, // Instante de cada nó em passos de dt
+    trajectory: Option<Vec<TimedState<D::State, D::Control>>>,
+}
+
+impl<M: ConfigurationMap, D: Dynamics<Config = M::State>> KinodynamicPlanner<M, D> {
+    pub fn new(start: D::State, goal: D::State, map: M, dynamics: D, dt: f64, goal_radius: f64) -> Self {
+        Self {
+            start,
+            goal,
+            model: Propagator::new(map, dynamics, dt),
+            seed: None,
+            rng: planner_rng(None),
+            control_samples: 10,
+            goal_radius,
+            goal_bias: 0.05,
+            max_iter: 20_000,
+            tree: Tree::new(start),
+            edges: vec![None],
+            steps_from_root: vec![0],
+            trajectory: None,
+        }
+    }
+
+    pub fn with_goal_bias(mut self, bias: f64) -> Self {
+        self.goal_bias = bias.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_iter(mut self, iters: u32) -> Self {
+        self.max_iter = iters;
+        self
+    }
+
+    // Pontos verificados entre dois passos de integração consecutivos (padrão: 1, só os passos)
+    pub fn with_collision_steps(mut self, steps: i32) -> Self {
+        self.model.collision_steps = steps.max(1);
+        self
+    }
+
+    // Duração de cada controle sorteado, em passos de dt (padrão: 1 a 10)
+    pub fn with_duration_steps(mut self, min_steps: usize, max_steps: usize) -> Self {
+        self.model.min_steps = min_steps.max(1);
+        self.model.max_steps = max_steps.max(self.model.min_steps);
+        self
+    }
+
+    // Controles sorteados por expansão (padrão: 10)
+    pub fn with_control_samples(mut self, samples: usize) -> Self {
+        self.control_samples = samples.max(1);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    pub fn map(&self) -> &M {
+        &self.model.map
+    }
+
+    pub fn space(&self) -> &D::Space {
+        &self.model.space
+    }
+
+    pub fn tree(&self) -> &Tree<D::State> {
+        &self.tree
+    }
+
+    pub fn trajectory(&self) -> Option<&Vec<TimedState<D::State, D::Control>>> {
+        self.trajectory.as_ref()
+    }
+
+    // Duração da trajetória encontrada
+    pub fn duration(&self) -> Option<f64> {
+        self.trajectory.as_ref().and_then(|t| t.last()).map(|p| p.time)
+    }
+
+    // Estado inicial dentro dos limites do modelo e livre no mapa; sem isso plan devolve None
+    pub fn start_is_free(&self) -> bool {
+        self.model.is_state_free(&self.start)
+    }
+
+    pub fn plan(&mut self) -> Option<&Vec<TimedState<D::State, D::Control>>> {
+        if !self.start_is_free() {
+            return None;
+        }
+
+        for _ in 0..self.max_iter {
+            let target = if self.rng.r#gen::<f64>() < self.goal_bias { self.goal } else { self.model.space.sample_uniform(&mut self.rng) };
+            let nearest_idx = self.tree.nearest(&self.model.space, &target);
+            let from = self.tree.nodes[nearest_idx].coord;
+
+            let Some((state, control, steps)) = self.model.random_extension(&from, &target, self.control_samples, &mut self.rng) else {
+                continue;
+            };
+            let new_idx = self.tree.add_node(&self.model.space, state, nearest_idx);
+            self.edges.push(Some((control, steps)));
+            self.steps_from_root.push(self.steps_from_root[nearest_idx] + steps);
+
+            if self.model.configuration_distance(&state, &self.goal) <= self.goal_radius {
+                self.trajectory = Some(self.build_trajectory(new_idx));
+                return self.trajectory.as_ref();
+            }
+        }
+        None
+    }
+
+    fn build_trajectory(&self, end_idx: usize) -> Vec<TimedState<D::State, D::Control>> {
+        let mut chain = Vec::new();
+        let mut current = Some(end_idx);
+        while let Some(idx) = current {
+            chain.push((self.tree.nodes[idx].coord, self.steps_from_root[idx], self.edges[idx]));
+            current = self.tree.nodes[idx].parent_idx;
+        }
+        chain.reverse();
+        self.model.trajectory(&chain)
+    }
+
+    // Descarta a árvore e a trajetória, recomeçando do estado inicial
+    // (e da mesma sequência aleatória, se houver semente)
+    pub fn reset(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.tree = Tree::new(self.start);
+        self.edges = vec![None];
+        self.steps_from_root = vec![0];
+        self.trajectory = None;
+    }
+
+    pub fn save_nodes_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        write_nodes_csv(filename, &[&self.tree])
+    }
+
+    pub fn save_trajectory_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        match &self.trajectory {
+            Some(trajectory) => write_trajectory_csv(filename, trajectory),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Nenhuma trajetória final para salvar.")),
+        }
+    }
+}
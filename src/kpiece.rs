@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::sampler::*;
+use crate::space::*;
+use crate::tree::*;
+
+// Célula da grade de projeção
+#[derive(Debug, Default)]
+struct Cell {
+    nodes: Vec<usize>,
+    selections: usize, // Quantas vezes a célula foi escolhida
+    failures: usize,   // Expansões que bateram em obstáculo
+}
+
+// Expansão guiada por densidade no estilo KPIECE: os nós são projetados em uma
+// grade e a árvore cresce a partir das células pouco visitadas, em especial as da
+// fronteira (células com vizinhas vazias)
+pub struct KPIECEPlanner<M: ConfigurationMap> {
+    start: M::State,
+    goal: M::State,
+    map: M,
+    space: M::Space,
+    sampler: Box<dyn Sampler<M>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    step_size: f64,
+    goal_radius: f64,
+    goal_bias: f64,
+    cell_size: f64,
+    max_iter: u32,
+    num_collision_check_steps: i32,
+
+    tree: Tree<M::State>,
+    cells: BTreeMap<Vec<i64>, Cell>,
+    path_found: Option<Vec<M::State>>,
+}
+
+impl<M: ConfigurationMap> KPIECEPlanner<M> {
+    pub fn new(start: M::State, goal: M::State, map: M, step_size: f64, goal_radius: f64, num_collision_check_steps: i32) -> Self {
+        let space = map.space();
+        let mut planner = Self {
+            start,
+            goal,
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            step_size,
+            goal_radius,
+            goal_bias: 0.05,
+            cell_size: 2.0 * step_size,
+            max_iter: 20_000,
+            num_collision_check_steps: num_collision_check_steps.max(1),
+            tree: Tree::new(start),
+            cells: BTreeMap::new(),
+            path_found: None,
+        };
+        planner.reset();
+        planner
+    }
+
+    pub fn with_goal_bias(mut self, bias: f64) -> Self {
+        self.goal_bias = bias.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_iter(mut self, iters: u32) -> Self {
+        self.max_iter = iters;
+        self
+    }
+
+    // Troca a fonte das amostras que dão a direção de expansão (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<M> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    // Lado das células da grade de projeção (padrão: 2 * step_size)
+    pub fn with_cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = cell_size;
+        self.reset();
+        self
+    }
+
+    fn project(&self, q: &M::State) -> Vec<i64> {
+        (0..M::State::DIM).map(|axis| (q.coord(axis) / self.cell_size).floor() as i64).collect()
+    }
+
+    // Célula de fronteira: alguma das 2 * d vizinhas ainda está vazia
+    fn is_exterior(&self, key: &[i64]) -> bool {
+        let mut neighbor = key.to_vec();
+        for axis in 0..key.len() {
+            for delta in [-1, 1] {
+                neighbor[axis] = key[axis] + delta;
+                if !self.cells.contains_key(&neighbor) {
+                    return true;
+                }
+            }
+            neighbor[axis] = key[axis];
+        }
+        false
+    }
+
+    // Importância da célula: maior para células de fronteira, pouco escolhidas,
+    // com poucos nós e poucas falhas
+    fn importance(&self, key: &[i64], cell: &Cell) -> f64 {
+        let exterior_boost = if self.is_exterior(key) { 4.0 } else { 1.0 };
+        exterior_boost / ((1 + cell.selections) as f64 * cell.nodes.len() as f64 * (1 + cell.failures) as f64)
+    }
+
+    // Sorteio de célula proporcional à importância, depois um nó uniforme dentro dela
+    fn select_node(&mut self) -> (Vec<i64>, usize) {
+        let scored: Vec<(&Vec<i64>, f64)> = self.cells.iter().map(|(k, c)| (k, self.importance(k, c))).collect();
+        let total: f64 = scored.iter().map(|(_, w)| w).sum();
+
+        let mut r = self.rng.gen_range(0.0..total);
+        let mut chosen = scored[scored.len() - 1].0.clone();
+        for (key, w) in &scored {
+            if r < *w {
+                chosen = (*key).clone();
+                break;
+            }
+            r -= w;
+        }
+
+        let cell = self.cells.get_mut(&chosen).unwrap();
+        cell.selections += 1;
+        let node = cell.nodes[self.rng.gen_range(0..cell.nodes.len())];
+        (chosen, node)
+    }
+
+    // Registra na grade um nó que já está na árvore
+    fn add_to_cell(&mut self, idx: usize) {
+        let key = self.project(&self.tree.nodes[idx].coord);
+        self.cells.entry(key).or_default().nodes.push(idx);
+    }
+
+    pub fn plan(&mut self) -> Option<&Vec<M::State>> {
+        for _ in 0..self.max_iter {
+            let (key, idx) = self.select_node();
+            let q = self.tree.nodes[idx].coord;
+            let target = goal_biased(self.sampler.as_mut(), &self.map, &self.space, &self.goal, self.goal_bias, &mut self.rng);
+            let q_new = self.space.steer(&q, &target, self.step_size);
+
+            if !self.map.is_motion_free(&q, &q_new, self.num_collision_check_steps) {
+                self.cells.get_mut(&key).unwrap().failures += 1;
+                continue;
+            }
+            let new_idx = self.tree.add_node(&self.space, q_new, idx);
+            self.add_to_cell(new_idx);
+
+            if self.space.distance(&q_new, &self.goal) <= self.goal_radius
+                && self.map.is_motion_free(&q_new, &self.goal, self.num_collision_check_steps)
+            {
+                let mut path = self.tree.reconstruct_path(new_idx);
+                if path.last() != Some(&self.goal) {
+                    path.push(self.goal);
+                }
+                self.path_found = Some(path);
+                return self.path_found.as_ref();
+            }
+        }
+        None
+    }
+
+    pub fn path_cost(&self) -> Option<f64> {
+        self.path_found.as_ref().map(|path| path_length(&self.space, path))
+    }
+
+    pub fn num_cells(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn tree(&self) -> &Tree<M::State> {
+        &self.tree
+    }
+
+    // Salva os nós da árvore no esquema comum dos planejadores
+    pub fn save_nodes_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        write_nodes_csv(filename, &[&self.tree])
+    }
+
+    // Salva o caminho final em um arquivo CSV
+    pub fn save_path_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        match &self.path_found {
+            Some(path) => write_path_csv(filename, path),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Nenhum caminho final para salvar.")),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.tree = Tree::new(self.start);
+        self.cells.clear();
+        self.path_found = None;
+        self.add_to_cell(0);
+    }
+}
@@ -0,0 +1,33 @@
+pub mod structs;
+pub mod readmap;
+pub mod rrt;
+pub mod rrt_connect;
+
+pub mod structs3d;
+pub mod readmap3d;
+pub mod rrt3d;
+
+pub mod space;
+pub mod kdtree;
+pub mod sampler;
+pub mod distance_field;
+pub mod footprint;
+pub mod se2;
+pub mod steering;
+pub mod dynamics;
+pub mod tree;
+pub mod planner;
+pub mod prm;
+pub mod fmt_star;
+pub mod bit_star;
+pub mod est;
+pub mod kpiece;
+pub mod kinodynamic;
+pub mod sst;
+pub mod geometry;
+pub mod arm_collision;
+pub mod roarm;
+pub mod scene;
+pub mod render;
+pub mod cli;
+pub mod persist;
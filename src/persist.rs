@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::io;
+
+// Formato dos arquivos de roadmap/árvore: CSV com a primeira coluna como tag
+//   kind,<tipo>           tipo do planejador que gerou o arquivo
+//   map,<hash>            impressão digital do mapa (FNV-1a em hexadecimal)
+//   param,<nome>,<valor>  parâmetros do planejador
+//   node,<c0>,<c1>,...    coordenadas de cada nó, na ordem dos índices
+//   edge,<i>,<j>,<custo>  arestas (em árvores: i = pai, j = filho)
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+// Hash FNV-1a incremental, usado como impressão digital dos mapas
+pub struct Fingerprint(u64);
+
+impl Default for Fingerprint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fingerprint {
+    pub fn new() -> Self {
+        Fingerprint(FNV_OFFSET)
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+pub fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+// Conteúdo de um arquivo salvo, já separado por tag
+#[derive(Debug, Default)]
+pub struct PlannerFile {
+    pub kind: String,
+    pub fingerprint: u64,
+    pub params: HashMap<String, String>,
+    pub nodes: Vec<Vec<f64>>,
+    pub edges: Vec<(usize, usize, f64)>,
+}
+
+impl PlannerFile {
+    pub fn new(kind: &str, fingerprint: u64) -> Self {
+        PlannerFile { kind: kind.to_string(), fingerprint, ..Default::default() }
+    }
+
+    pub fn set_param(&mut self, name: &str, value: impl ToString) {
+        self.params.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn param<T: std::str::FromStr>(&self, name: &str) -> io::Result<T> {
+        self.params
+            .get(name)
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| invalid_data(format!("Parâmetro '{}' ausente ou inválido.", name)))
+    }
+
+    pub fn save(&self, filename: &str) -> io::Result<()> {
+        let mut w = csv::WriterBuilder::new().flexible(true).from_path(filename)?;
+        w.write_record(["kind", &self.kind])?;
+        w.write_record(["map", &format!("{:016x}", self.fingerprint)])?;
+
+        // Ordem fixa para que o mesmo planejador gere o mesmo arquivo
+        let mut params: Vec<_> = self.params.iter().collect();
+        params.sort();
+        for (name, value) in params {
+            w.write_record(["param", name, value])?;
+        }
+        for node in &self.nodes {
+            let mut record = vec!["node".to_string()];
+            record.extend(node.iter().map(|c| c.to_string()));
+            w.write_record(&record)?;
+        }
+        for (i, j, cost) in &self.edges {
+            w.write_record(["edge", &i.to_string(), &j.to_string(), &cost.to_string()])?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+
+    pub fn load(filename: &str) -> io::Result<Self> {
+        let mut r = csv::ReaderBuilder::new().flexible(true).has_headers(false).from_path(filename)?;
+        let mut file = PlannerFile::default();
+        let mut has_fingerprint = false;
+
+        for record in r.records() {
+            let record = record?;
+            let field = |i: usize| record.get(i).ok_or_else(|| invalid_data("Registro incompleto no arquivo."));
+            match field(0)? {
+                "kind" => file.kind = field(1)?.to_string(),
+                "map" => {
+                    file.fingerprint = u64::from_str_radix(field(1)?, 16)
+                        .map_err(|_| invalid_data("Impressão digital do mapa inválida."))?;
+                    has_fingerprint = true;
+                }
+                "param" => {
+                    file.params.insert(field(1)?.to_string(), field(2)?.to_string());
+                }
+                "node" => {
+                    let coords = record
+                        .iter()
+                        .skip(1)
+                        .map(|c| c.parse::<f64>())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|_| invalid_data("Coordenada inválida em registro 'node'."))?;
+                    file.nodes.push(coords);
+                }
+                "edge" => {
+                    let parse_idx = |s: &str| s.parse::<usize>().map_err(|_| invalid_data("Índice inválido em registro 'edge'."));
+                    let i = parse_idx(field(1)?)?;
+                    let j = parse_idx(field(2)?)?;
+                    let cost = field(3)?.parse::<f64>().map_err(|_| invalid_data("Custo inválido em registro 'edge'."))?;
+                    if i >= file.nodes.len() || j >= file.nodes.len() {
+                        return Err(invalid_data("Aresta referencia um nó inexistente."));
+                    }
+                    file.edges.push((i, j, cost));
+                }
+                tag => return Err(invalid_data(format!("Tag desconhecida no arquivo: '{}'.", tag))),
+            }
+        }
+
+        if !has_fingerprint {
+            return Err(invalid_data("Arquivo sem impressão digital do mapa."));
+        }
+        Ok(file)
+    }
+
+    // Rejeita arquivos de outro tipo de planejador ou gerados para outro mapa
+    pub fn check(&self, kind: &str, fingerprint: u64, dim: usize) -> io::Result<()> {
+        if self.kind != kind {
+            return Err(invalid_data(format!("Arquivo é do tipo '{}', esperado '{}'.", self.kind, kind)));
+        }
+        if self.fingerprint != fingerprint {
+            return Err(invalid_data(format!(
+                "Arquivo gerado para outro mapa (impressão digital {:016x}, mapa atual {:016x}).",
+                self.fingerprint, fingerprint
+            )));
+        }
+        if self.nodes.iter().any(|n| n.len() != dim) {
+            return Err(invalid_data(format!("Nós com dimensão diferente de {}.", dim)));
+        }
+        Ok(())
+    }
+}
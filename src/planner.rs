@@ -0,0 +1,327 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::persist::*;
+use crate::sampler::*;
+use crate::space::*;
+use crate::tree::*;
+
+// Planejador RRT / RRT* / Informed RRT* genérico, para qualquer mapa e espaço de estados.
+// RRTPlanner (2D) e RRTPlanner3D são apenas instâncias deste tipo.
+pub struct TreePlanner<M: ConfigurationMap> {
+    start: M::State,
+    goal: M::State,
+    map: M,
+    space: M::Space,
+    sampler: Box<dyn Sampler<M>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    step_size: f64,
+    goal_radius: f64,
+    goal_bias: f64,
+    max_iter: u32,
+    collision_steps: i32,
+
+    tree: Tree<M::State>,
+    path_found: Option<Vec<M::State>>,
+}
+
+impl<M: ConfigurationMap> TreePlanner<M> {
+    pub fn new(start: M::State, goal: M::State, map: M, step_size: f64, goal_radius: f64) -> Self {
+        let space = map.space();
+        Self {
+            start,
+            goal,
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            step_size,
+            goal_radius,
+            goal_bias: 0.05,
+            max_iter: 20_000,
+            collision_steps: 20,
+            tree: Tree::new(start),
+            path_found: None,
+        }
+    }
+
+    pub fn with_goal_bias(mut self, bias: f64) -> Self {
+        self.goal_bias = bias.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_iter(mut self, iters: u32) -> Self {
+        self.max_iter = iters;
+        self
+    }
+
+    pub fn with_collision_steps(mut self, steps: i32) -> Self {
+        self.collision_steps = steps.max(1);
+        self
+    }
+
+    // Troca a fonte de amostras (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<M> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    pub fn map(&self) -> &M {
+        &self.map
+    }
+
+    pub fn space(&self) -> &M::Space {
+        &self.space
+    }
+
+    pub fn tree(&self) -> &Tree<M::State> {
+        &self.tree
+    }
+
+    pub fn path(&self) -> Option<&Vec<M::State>> {
+        self.path_found.as_ref()
+    }
+
+    // Amostra com viés para o objetivo; com c_best, só no subconjunto informado
+    fn sample(&mut self, c_best: Option<f64>) -> M::State {
+        if self.rng.r#gen::<f64>() < self.goal_bias {
+            return self.goal;
+        }
+        match c_best {
+            Some(c) => self.space.sample_informed(&self.start, &self.goal, c, &mut self.rng),
+            None => self.sampler.sample(&self.map, &self.space, &mut self.rng),
+        }
+    }
+
+    fn is_motion_free(&self, a: &M::State, b: &M::State) -> bool {
+        self.map.is_motion_free(a, b, self.collision_steps)
+    }
+
+    fn finish_path(&mut self, end_idx: usize) -> Option<&Vec<M::State>> {
+        let mut path = self.tree.reconstruct_path(end_idx);
+        if path.last() != Some(&self.goal) {
+            path.push(self.goal);
+        }
+        self.path_found = Some(path);
+        self.path_found.as_ref()
+    }
+
+    // RRT clássico: para no primeiro nó que entra em goal_radius
+    pub fn plan(&mut self) -> Option<&Vec<M::State>> {
+        for _ in 0..self.max_iter {
+            let q_rand = self.sample(None);
+            let nearest_idx = self.tree.nearest(&self.space, &q_rand);
+            let q_near = self.tree.nodes[nearest_idx].coord;
+            let q_new = self.space.steer(&q_near, &q_rand, self.step_size);
+
+            if self.is_motion_free(&q_near, &q_new) {
+                let new_idx = self.tree.add_node(&self.space, q_new, nearest_idx);
+                if self.space.distance(&q_new, &self.goal) <= self.goal_radius {
+                    return self.finish_path(new_idx);
+                }
+            }
+        }
+        None
+    }
+
+    // Raio de vizinhança do RRT*: gamma * (ln n / n)^(1/d), limitado pelo step size,
+    // com gamma > 2 (1 + 1/d)^(1/d) (volume / volume da bola unitária)^(1/d)
+    fn rewire_radius(&self) -> f64 {
+        let d = M::State::DIM as f64;
+        let n = (self.tree.len() + 1) as f64;
+        let gamma = 2.0 * (1.0 + 1.0 / d).powf(1.0 / d) * (self.space.volume() / unit_ball_volume(M::State::DIM)).powf(1.0 / d);
+        (gamma * (n.ln() / n).powf(1.0 / d)).min(self.step_size)
+    }
+
+    // Nó próximo do objetivo com menor custo total (custo do nó + distância ao objetivo)
+    fn best_goal_node(&self, goal_nodes: &[usize]) -> Option<(usize, f64)> {
+        goal_nodes
+            .iter()
+            .map(|&i| (i, self.tree.nodes[i].cost + self.space.distance(&self.tree.nodes[i].coord, &self.goal)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    // RRT*: escolhe o pai de menor custo na vizinhança e religa os vizinhos.
+    // Não para no primeiro caminho, continua refinando até max_iter.
+    pub fn plan_star(&mut self) -> Option<&Vec<M::State>> {
+        self.run_star(false)
+    }
+
+    // Informed RRT*: igual ao RRT*, mas depois da primeira solução só amostra
+    // no hiperesferoide que pode conter caminhos melhores
+    pub fn plan_informed_star(&mut self) -> Option<&Vec<M::State>> {
+        self.run_star(true)
+    }
+
+    fn run_star(&mut self, informed: bool) -> Option<&Vec<M::State>> {
+        let mut goal_nodes: Vec<usize> = Vec::new();
+        let c_min = self.space.distance(&self.start, &self.goal);
+
+        for _ in 0..self.max_iter {
+            let c_best = if informed { self.best_goal_node(&goal_nodes).map(|(_, c)| c) } else { None };
+            // Caminho já na reta start-goal: o subconjunto informado é só o segmento e não há o que refinar
+            if c_best.is_some_and(|c| c <= c_min + 1e-9) {
+                break;
+            }
+            let q_rand = self.sample(c_best);
+            let nearest_idx = self.tree.nearest(&self.space, &q_rand);
+            let q_near = self.tree.nodes[nearest_idx].coord;
+            let q_new = self.space.steer(&q_near, &q_rand, self.step_size);
+
+            if !self.is_motion_free(&q_near, &q_new) {
+                continue;
+            }
+
+            let near = self.tree.near(&self.space, &q_new, self.rewire_radius());
+
+            // Escolhe o pai que minimiza o custo até q_new
+            let mut parent = nearest_idx;
+            let mut new_cost = self.tree.nodes[nearest_idx].cost + self.space.distance(&q_near, &q_new);
+            for &i in &near {
+                let node = &self.tree.nodes[i];
+                let cost = node.cost + self.space.distance(&node.coord, &q_new);
+                if cost < new_cost && self.is_motion_free(&node.coord, &q_new) {
+                    parent = i;
+                    new_cost = cost;
+                }
+            }
+            let new_idx = self.tree.add_node(&self.space, q_new, parent);
+
+            // Religa os vizinhos que ficam mais baratos passando por q_new
+            for &i in &near {
+                if i == parent {
+                    continue;
+                }
+                let coord = self.tree.nodes[i].coord;
+                let cost = new_cost + self.space.distance(&q_new, &coord);
+                if cost < self.tree.nodes[i].cost && self.is_motion_free(&q_new, &coord) {
+                    self.tree.rewire(&self.space, i, new_idx);
+                }
+            }
+
+            if self.space.distance(&q_new, &self.goal) <= self.goal_radius {
+                goal_nodes.push(new_idx);
+            }
+        }
+
+        // O custo dos nós do objetivo pode ter caído com o religamento, então escolhe no final
+        let (best, _) = self.best_goal_node(&goal_nodes)?;
+        self.finish_path(best)
+    }
+
+    // Comprimento do caminho encontrado (para comparar RRT e RRT*)
+    pub fn path_cost(&self) -> Option<f64> {
+        self.path_found.as_ref().map(|path| path_length(&self.space, path))
+    }
+
+    // Descarta a árvore e o caminho, recomeçando do ponto inicial
+    // (e da mesma sequência aleatória, se houver semente)
+    pub fn reset(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.tree = Tree::new(self.start);
+        self.path_found = None;
+    }
+
+    pub fn save_nodes_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        write_nodes_csv(filename, &[&self.tree])
+    }
+
+    pub fn save_path_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        match &self.path_found {
+            Some(path) => write_path_csv(filename, path),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Nenhum caminho final para salvar.")),
+        }
+    }
+
+    fn file_kind() -> String {
+        format!("rrt{}d", M::State::DIM)
+    }
+
+    // Salva a árvore (com impressão digital do mapa e parâmetros) para ser recarregada depois
+    pub fn save_tree(&self, filename: &str) -> std::io::Result<()> {
+        let mut file = PlannerFile::new(&Self::file_kind(), self.map.fingerprint());
+        for axis in 0..M::State::DIM {
+            file.set_param(&format!("start_{}", M::State::axis_name(axis)), self.start.coord(axis));
+        }
+        file.set_param("step_size", self.step_size);
+        file.set_param("goal_radius", self.goal_radius);
+        file.set_param("goal_bias", self.goal_bias);
+        file.set_param("max_iter", self.max_iter);
+        file.set_param("collision_steps", self.collision_steps);
+
+        for (i, node) in self.tree.nodes().iter().enumerate() {
+            file.nodes.push(node.coord.coords());
+            if let Some(p) = node.parent_idx {
+                file.edges.push((p, i, node.cost - self.tree.nodes[p].cost));
+            }
+        }
+        file.save(filename)
+    }
+
+    // Carrega uma árvore salva. Rejeita arquivos de outro mapa ou com outra raiz.
+    pub fn load_tree(&mut self, filename: &str) -> std::io::Result<()> {
+        let file = PlannerFile::load(filename)?;
+        file.check(&Self::file_kind(), self.map.fingerprint(), M::State::DIM)?;
+
+        let start_coords = (0..M::State::DIM)
+            .map(|axis| file.param::<f64>(&format!("start_{}", M::State::axis_name(axis))))
+            .collect::<std::io::Result<Vec<f64>>>()?;
+        let start = M::State::from_coords(&start_coords);
+        let root = file.nodes.first().map(|n| M::State::from_coords(n));
+        if start != self.start || root != Some(start) {
+            return Err(invalid_data("A árvore salva não tem raiz no ponto inicial deste planejador."));
+        }
+
+        let coords: Vec<M::State> = file.nodes.iter().map(|n| M::State::from_coords(n)).collect();
+        let parents = parents_from_edges(coords.len(), &file.edges)?;
+
+        self.step_size = file.param("step_size")?;
+        self.goal_radius = file.param("goal_radius")?;
+        self.goal_bias = file.param("goal_bias")?;
+        self.max_iter = file.param("max_iter")?;
+        self.collision_steps = file.param("collision_steps")?;
+        self.tree = Tree::from_parents(&self.space, coords, parents);
+        self.path_found = None;
+        Ok(())
+    }
+}
+
+// Pai de cada nó a partir das arestas (pai, filho) de um arquivo. Cada nó além da raiz
+// tem exatamente um pai e todos chegam à raiz (sem ciclos nem pedaços soltos).
+fn parents_from_edges(len: usize, edges: &[(usize, usize, f64)]) -> std::io::Result<Vec<Option<usize>>> {
+    let mut parents = vec![None; len];
+    let mut children = vec![Vec::new(); len];
+    for &(parent_idx, child_idx, _) in edges {
+        if child_idx == 0 || parent_idx == child_idx {
+            return Err(invalid_data(format!("Aresta inválida na árvore salva: {} -> {}.", parent_idx, child_idx)));
+        }
+        if parents[child_idx].replace(parent_idx).is_some() {
+            return Err(invalid_data(format!("Nó {} com mais de um pai na árvore salva.", child_idx)));
+        }
+        children[parent_idx].push(child_idx);
+    }
+    if parents.iter().skip(1).any(Option::is_none) {
+        return Err(invalid_data("Nó sem pai na árvore salva."));
+    }
+
+    // Com um pai por nó, os nós que não são alcançados a partir da raiz formam ciclos
+    let mut reached = 0;
+    let mut stack = vec![0];
+    while let Some(i) = stack.pop() {
+        reached += 1;
+        stack.extend(children[i].iter().copied());
+    }
+    if reached != len {
+        return Err(invalid_data("A árvore salva tem ciclos: nem todos os nós chegam à raiz."));
+    }
+    Ok(parents)
+}
@@ -0,0 +1,408 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::kdtree::KdTree;
+use rand::rngs::StdRng;
+
+use crate::sampler::*;
+use crate::space::*;
+use crate::persist::*;
+
+// Estratégia de conexão entre os nós do roadmap
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Connection {
+    KNearest(usize), // Conecta aos k vizinhos mais próximos
+    Radius(f64),     // Conecta a todos os vizinhos dentro de um raio fixo
+    PRMStar,         // Raio que encolhe com n: gamma * (ln n / n)^(1/d)
+}
+
+impl Connection {
+    fn to_param(self) -> String {
+        match self {
+            Connection::KNearest(k) => format!("knearest:{}", k),
+            Connection::Radius(r) => format!("radius:{}", r),
+            Connection::PRMStar => "prmstar".to_string(),
+        }
+    }
+
+    fn from_param(s: &str) -> Option<Connection> {
+        match s.split_once(':') {
+            Some(("knearest", k)) => k.parse().ok().map(Connection::KNearest),
+            Some(("radius", r)) => r.parse().ok().map(Connection::Radius),
+            None if s == "prmstar" => Some(Connection::PRMStar),
+            _ => None,
+        }
+    }
+}
+
+// Entrada da fila de prioridade (menor custo primeiro)
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct HeapEntry {
+    pub cost: f64,
+    pub idx: usize,
+}
+
+impl PartialEq for HeapEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for HeapEntry {}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Invertido para o BinaryHeap funcionar como min-heap
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+// A* sobre uma lista de adjacência, com a distância do espaço como heurística
+pub(crate) fn astar<Sp: StateSpace>(space: &Sp, nodes: &[Sp::State], edges: &[Vec<(usize, f64)>], start: usize, goal: usize) -> Option<Vec<usize>> {
+    let mut g = vec![f64::INFINITY; nodes.len()];
+    let mut came_from: Vec<Option<usize>> = vec![None; nodes.len()];
+    let mut open = BinaryHeap::new();
+
+    g[start] = 0.0;
+    open.push(HeapEntry { cost: space.distance(&nodes[start], &nodes[goal]), idx: start });
+
+    while let Some(HeapEntry { cost, idx }) = open.pop() {
+        if idx == goal {
+            let mut path = vec![goal];
+            let mut current = goal;
+            while let Some(prev) = came_from[current] {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        // Entrada desatualizada
+        if cost > g[idx] + space.distance(&nodes[idx], &nodes[goal]) {
+            continue;
+        }
+        for &(next, weight) in &edges[idx] {
+            let tentative = g[idx] + weight;
+            if tentative < g[next] {
+                g[next] = tentative;
+                came_from[next] = Some(idx);
+                open.push(HeapEntry { cost: tentative + space.distance(&nodes[next], &nodes[goal]), idx: next });
+            }
+        }
+    }
+    None
+}
+
+// Contadores de verificações de colisão, para comparar PRM e Lazy PRM
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CheckCounts {
+    pub node_checks: usize,
+    pub edge_checks: usize,
+}
+
+// Chave de uma aresta não direcionada
+fn edge_key(i: usize, j: usize) -> (usize, usize) {
+    if i < j { (i, j) } else { (j, i) }
+}
+
+// PRM multi-consulta: o roadmap é construído uma vez e respondido várias vezes com A*.
+// No modo lazy as arestas só são verificadas quando aparecem em um caminho candidato.
+pub struct PRMPlanner<M: ConfigurationMap> {
+    map: M,
+    space: M::Space,
+    sampler: Box<dyn Sampler<M>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    num_samples: usize,
+    connection: Connection,
+    num_collision_check_steps: i32,
+    lazy: bool,
+
+    pub nodes: Vec<M::State>,
+    pub edges: Vec<Vec<(usize, f64)>>, // Lista de adjacência: (vizinho, custo)
+    index: KdTree<M::State>,            // Índice espacial dos nós, para as buscas de vizinhos
+    validated: HashSet<(usize, usize)>, // Arestas já verificadas no modo lazy
+    checks: CheckCounts,
+}
+
+impl<M: ConfigurationMap> PRMPlanner<M> {
+    pub fn new(map: M, num_samples: usize, num_collision_check_steps: i32) -> Self {
+        let space = map.space();
+        Self {
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            num_samples,
+            connection: Connection::PRMStar,
+            num_collision_check_steps: num_collision_check_steps.max(1),
+            lazy: false,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            index: KdTree::new(),
+            validated: HashSet::new(),
+            checks: CheckCounts::default(),
+        }
+    }
+
+    pub fn with_connection(mut self, connection: Connection) -> Self {
+        self.connection = connection;
+        self
+    }
+
+    pub fn with_lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    // Troca a fonte das amostras do roadmap (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<M> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    // Verificações de colisão feitas desde a criação (ou do último reset_checks)
+    pub fn checks(&self) -> CheckCounts {
+        self.checks
+    }
+
+    pub fn reset_checks(&mut self) {
+        self.checks = CheckCounts::default();
+    }
+
+    fn check_state(&mut self, state: &M::State) -> bool {
+        self.checks.node_checks += 1;
+        self.map.is_free(state)
+    }
+
+    fn check_motion(&mut self, a: &M::State, b: &M::State) -> bool {
+        self.checks.edge_checks += 1;
+        self.map.is_motion_free(a, b, self.num_collision_check_steps)
+    }
+
+    // Conecta dois nós; no modo lazy a aresta entra sem verificação
+    fn try_connect(&mut self, i: usize, j: usize, cost: f64) {
+        let (a, b) = (self.nodes[i], self.nodes[j]);
+        if self.lazy || self.check_motion(&a, &b) {
+            self.edges[i].push((j, cost));
+            self.edges[j].push((i, cost));
+        }
+    }
+
+    fn remove_edge(&mut self, i: usize, j: usize) {
+        self.edges[i].retain(|&(k, _)| k != j);
+        self.edges[j].retain(|&(k, _)| k != i);
+    }
+
+    pub fn map(&self) -> &M {
+        &self.map
+    }
+
+    // Raio do PRM*: gamma > 2 (1 + 1/d)^(1/d) (volume / volume da bola unitária)^(1/d)
+    fn prm_star_radius(&self, n: usize) -> f64 {
+        let d = M::State::DIM as f64;
+        let n = n.max(2) as f64;
+        let gamma = 2.0 * (1.0 + 1.0 / d).powf(1.0 / d) * (self.space.volume() / unit_ball_volume(M::State::DIM)).powf(1.0 / d);
+        gamma * (n.ln() / n).powf(1.0 / d)
+    }
+
+    // Candidatos a vizinho de um estado, em ordem crescente de distância
+    fn neighbor_candidates(&self, state: &M::State, n: usize) -> Vec<(usize, f64)> {
+        let mut candidates = match self.connection {
+            // Um a mais, já que o próprio estado pode estar no índice
+            Connection::KNearest(k) => self.index.k_nearest(&self.space, state, k + 1),
+            Connection::Radius(r) => self.index.within_radius(&self.space, state, r),
+            Connection::PRMStar => self.index.within_radius(&self.space, state, self.prm_star_radius(n)),
+        };
+        candidates.retain(|&(_, d)| d > 0.0);
+        if let Connection::KNearest(k) = self.connection {
+            candidates.truncate(k);
+        }
+        candidates
+    }
+
+    // Amostra configurações livres e conecta os vizinhos (com arestas sem colisão,
+    // ou sem verificar nada no modo lazy)
+    pub fn build_roadmap(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.nodes.clear();
+        self.edges.clear();
+        self.validated.clear();
+
+        // Com o espaço livre pequeno (ou vazio) desiste depois de MAX_ATTEMPTS por amostra
+        // e monta o roadmap com o que conseguiu
+        for _ in 0..self.num_samples * MAX_ATTEMPTS {
+            if self.nodes.len() >= self.num_samples {
+                break;
+            }
+            let q = self.sampler.sample(&self.map, &self.space, &mut self.rng);
+            if self.check_state(&q) {
+                self.nodes.push(q);
+            }
+        }
+        self.index = KdTree::from_points(self.nodes.clone());
+        self.edges = vec![Vec::new(); self.nodes.len()];
+
+        // Cada par é testado uma única vez, mesmo que a primeira tentativa tenha falhado
+        // (a relação de vizinhança com k vizinhos não é simétrica)
+        let mut tested: HashSet<(usize, usize)> = HashSet::new();
+        for i in 0..self.nodes.len() {
+            let q = self.nodes[i];
+            for (j, d) in self.neighbor_candidates(&q, self.nodes.len()) {
+                if tested.insert(edge_key(i, j)) {
+                    self.try_connect(i, j, d);
+                }
+            }
+        }
+    }
+
+    // Liga um estado de consulta ao roadmap, retornando o índice do novo nó
+    fn attach(&mut self, state: M::State) -> usize {
+        let idx = self.nodes.len();
+        let neighbors = self.neighbor_candidates(&state, self.nodes.len());
+        self.nodes.push(state);
+        self.index.insert(state);
+        self.edges.push(Vec::new());
+
+        for (j, d) in neighbors {
+            self.try_connect(idx, j, d);
+        }
+        idx
+    }
+
+    // Remove os nós de consulta adicionados a partir de `len`
+    fn detach(&mut self, len: usize) {
+        self.nodes.truncate(len);
+        self.index.truncate(len);
+        self.edges.truncate(len);
+        for edges in &mut self.edges {
+            edges.retain(|&(j, _)| j < len);
+        }
+        self.validated.retain(|&(_, j)| j < len);
+    }
+
+    // Lazy PRM: verifica só as arestas do caminho candidato; se alguma colide,
+    // remove a aresta do grafo e busca de novo
+    fn lazy_search(&mut self, start_idx: usize, goal_idx: usize) -> Option<Vec<usize>> {
+        'search: loop {
+            let indices = astar(&self.space, &self.nodes, &self.edges, start_idx, goal_idx)?;
+            for w in indices.windows(2) {
+                let key = edge_key(w[0], w[1]);
+                if self.validated.contains(&key) {
+                    continue;
+                }
+                let (a, b) = (self.nodes[w[0]], self.nodes[w[1]]);
+                if self.check_motion(&a, &b) {
+                    self.validated.insert(key);
+                } else {
+                    self.remove_edge(w[0], w[1]);
+                    continue 'search;
+                }
+            }
+            return Some(indices);
+        }
+    }
+
+    // Responde uma consulta início/objetivo. Arestas invalidadas no modo lazy
+    // continuam removidas nas próximas consultas.
+    pub fn query(&mut self, start: M::State, goal: M::State) -> Option<Vec<M::State>> {
+        if self.nodes.is_empty() {
+            self.build_roadmap();
+        }
+        if !self.check_state(&start) || !self.check_state(&goal) {
+            return None;
+        }
+
+        let len = self.nodes.len();
+        let start_idx = self.attach(start);
+        let goal_idx = self.attach(goal);
+
+        let indices = if self.lazy {
+            self.lazy_search(start_idx, goal_idx)
+        } else {
+            astar(&self.space, &self.nodes, &self.edges, start_idx, goal_idx)
+        };
+        let path = indices.map(|indices| indices.into_iter().map(|i| self.nodes[i]).collect());
+
+        self.detach(len);
+        path
+    }
+
+    // Salva o roadmap com a impressão digital do mapa e os parâmetros usados
+    pub fn save_roadmap(&self, filename: &str) -> std::io::Result<()> {
+        let mut file = PlannerFile::new("prm", self.map.fingerprint());
+        file.set_param("dim", M::State::DIM);
+        file.set_param("num_samples", self.num_samples);
+        file.set_param("connection", self.connection.to_param());
+        file.set_param("collision_steps", self.num_collision_check_steps);
+        file.set_param("lazy", self.lazy);
+
+        file.nodes = self
+            .nodes
+            .iter()
+            .map(|n| (0..M::State::DIM).map(|axis| n.coord(axis)).collect())
+            .collect();
+        for (i, edges) in self.edges.iter().enumerate() {
+            for &(j, cost) in edges {
+                if i < j {
+                    file.edges.push((i, j, cost));
+                }
+            }
+        }
+        file.save(filename)
+    }
+
+    // Carrega um roadmap salvo; falha se ele foi construído para outro mapa ou se o grafo
+    // está corrompido (laços, arestas repetidas). Nada muda no planejador em caso de erro.
+    pub fn load_roadmap(&mut self, filename: &str) -> std::io::Result<()> {
+        let file = PlannerFile::load(filename)?;
+        file.check("prm", self.map.fingerprint(), M::State::DIM)?;
+
+        let connection = Connection::from_param(&file.param::<String>("connection")?)
+            .ok_or_else(|| invalid_data("Estratégia de conexão inválida."))?;
+        let num_samples = file.param("num_samples")?;
+        let num_collision_check_steps = file.param("collision_steps")?;
+        let lazy = file.param("lazy")?;
+
+        let mut seen = HashSet::new();
+        let mut edges = vec![Vec::new(); file.nodes.len()];
+        for &(i, j, cost) in &file.edges {
+            if i == j {
+                return Err(invalid_data(format!("Aresta do nó {} para ele mesmo.", i)));
+            }
+            if !seen.insert(edge_key(i, j)) {
+                return Err(invalid_data(format!("Aresta {}-{} repetida.", i, j)));
+            }
+            edges[i].push((j, cost));
+            edges[j].push((i, cost));
+        }
+
+        self.num_samples = num_samples;
+        self.num_collision_check_steps = num_collision_check_steps;
+        self.connection = connection;
+        self.lazy = lazy;
+        self.validated.clear();
+        self.nodes = file.nodes.iter().map(|c| M::State::from_coords(c)).collect();
+        self.index = KdTree::from_points(self.nodes.clone());
+        self.edges = edges;
+        Ok(())
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edges.iter().map(|e| e.len()).sum::<usize>() / 2
+    }
+}
@@ -1,8 +1,5 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
 use crate::structs::*;
+use crate::persist::{invalid_data, Fingerprint};
 pub use image::{ImageBuffer, Rgb};
 
 pub fn is_black(pixel: &Rgb<u8>) -> bool {
@@ -72,26 +69,47 @@ pub fn find_bounds(image: &ImageBuffer<Rgb<u8>, Vec<u8>>) -> (u32, u32, u32, u32
 pub struct OccupancyMap {
     pub image: ImageBuffer<Rgb<u8>, Vec<u8>>,
     pub pixel_bounds: (u32, u32, u32, u32), // (x_min, y_min, x_max, y_max)
+    pub original_image: Option<ImageBuffer<Rgb<u8>, Vec<u8>>>, // Imagem antes da inflação (None se não inflado)
 }
 
 impl OccupancyMap {
     pub fn new(file_path: &str) -> Self {
-        let img = image::open(file_path).expect("Não foi possível abrir a imagem do mapa.");
+        Self::try_new(file_path).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Como new, mas devolve erro em vez de abortar quando a imagem não abre
+    pub fn try_new(file_path: &str) -> std::io::Result<Self> {
+        let img = image::open(file_path)
+            .map_err(|e| invalid_data(format!("Não foi possível abrir a imagem do mapa {}: {}", file_path, e)))?;
         let rgb_img = img.to_rgb8();
 
         let pixel_bounds = find_bounds(&rgb_img);
         println!("Limites de navegação detectados (pixels): {:?}", pixel_bounds);
 
-        OccupancyMap {
+        Ok(OccupancyMap {
             image: rgb_img,
             pixel_bounds,
-        }
+            original_image: None,
+        })
+    }
+
+    // Imagem para visualização: a original, mesmo que o mapa tenha sido inflado
+    pub fn display_image(&self) -> &ImageBuffer<Rgb<u8>, Vec<u8>> {
+        self.original_image.as_ref().unwrap_or(&self.image)
+    }
+
+    // Impressão digital do mapa (dimensões + pixels), usada para validar roadmaps salvos
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = Fingerprint::new();
+        let (width, height) = self.image.dimensions();
+        hasher.write(&width.to_le_bytes());
+        hasher.write(&height.to_le_bytes());
+        hasher.write(self.image.as_raw());
+        hasher.finish()
     }
 
     // Verifica se um ponto específico está em um obstáculo (pixel preto)
     pub fn is_obstructed(&self, point: &Point) -> bool {
-        let (x_min_px, y_min_px, x_max_px, y_max_px) = self.pixel_bounds;
-        
         // Arredondamos para o pixel mais próximo para obter a coordenada inteira.
         let px = point.x.round() as u32;
         let py = point.y.round() as u32;
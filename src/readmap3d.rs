@@ -1,6 +1,7 @@
 use image::{ImageBuffer, Rgb};
 use crate::readmap::is_black;
 use crate::structs3d::Point3;
+use crate::persist::{invalid_data, Fingerprint};
 
 pub struct OccupancyMap3D {
     // Vetor de camadas: [z][y][x]
@@ -9,51 +10,72 @@ pub struct OccupancyMap3D {
     pub height: u32,
     pub depth: usize, // número de slices
     pub dz: f64,      // espessura
+    pub original_layers: Option<Vec<ImageBuffer<Rgb<u8>, Vec<u8>>>>, // Camadas antes da inflação (None se não inflado)
 }
 
 impl OccupancyMap3D {
     
     pub fn replicate_from(file_path: &str, depth: usize, dz: f64) -> Self {
-        let img = image::open(file_path).expect("Não foi possível abrir a imagem do mapa (3D).").to_rgb8();
+        Self::try_replicate_from(file_path, depth, dz).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Como replicate_from, mas devolve erro em vez de abortar quando a imagem não abre
+    pub fn try_replicate_from(file_path: &str, depth: usize, dz: f64) -> std::io::Result<Self> {
+        let img = image::open(file_path)
+            .map_err(|e| invalid_data(format!("Não foi possível abrir a imagem do mapa (3D) {}: {}", file_path, e)))?
+            .to_rgb8();
         let (width, height) = img.dimensions();
         let mut layers = Vec::with_capacity(depth);
         for _ in 0..depth {
             layers.push(img.clone());
         }
 
-        Self {
+        Ok(Self {
             layers,
             width,
             height,
             depth,
             dz,
-        }
+            original_layers: None,
+        })
+    }
+
+    // Camadas para visualização: as originais, mesmo que o mapa tenha sido inflado
+    pub fn display_layers(&self) -> &[ImageBuffer<Rgb<u8>, Vec<u8>>] {
+        self.original_layers.as_deref().unwrap_or(&self.layers)
     }
 
-    /*
-    #[allow(dead_code)]
+    // Uma imagem por camada, de baixo para cima; todas precisam ter o mesmo tamanho
     pub fn from_files(paths: &[&str], dz: f64) -> Self {
-        assert!(!paths.is_empty(), "É necessário pelo menos um arquivo para as camadas");
+        Self::try_from_files(paths, dz).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // Como from_files, mas devolve erro para lista vazia, imagem que não abre ou tamanhos diferentes
+    pub fn try_from_files(paths: &[&str], dz: f64) -> std::io::Result<Self> {
+        if paths.is_empty() {
+            return Err(invalid_data("É necessário pelo menos um arquivo para as camadas"));
+        }
         let mut layers: Vec<ImageBuffer<Rgb<u8>, Vec<u8>>> = Vec::with_capacity(paths.len());
         for p in paths {
             layers.push(
-                image::open(p).unwrap_or_else(|_| panic!("Falha ao abrir a imagem da camada: {p}")).to_rgb8(),
+                image::open(p).map_err(|e| invalid_data(format!("Falha ao abrir a imagem da camada {p}: {e}")))?.to_rgb8(),
             );
         }
         let (width, height) = layers[0].dimensions();
         for (i, l) in layers.iter().enumerate() {
-            let (w, h) = l.dimensions();
-            assert_eq!((w, h),(width, height),"Todas as camadas devem ter o mesmo tamanho. Camada {i} difere.");
+            if l.dimensions() != (width, height) {
+                return Err(invalid_data(format!("Todas as camadas devem ter o mesmo tamanho. Camada {i} difere.")));
+            }
         }
-        Self {
+        Ok(Self {
+            depth: layers.len(),
             layers,
             width,
             height,
-            depth: layers.len(),
             dz,
-        }
+            original_layers: None,
+        })
     }
-     */
 
     #[inline]
     fn clamp_xy(&self, x: i32, y: i32) -> Option<(u32, u32)> {
@@ -73,6 +95,19 @@ impl OccupancyMap3D {
         }
     }
 
+    // Impressão digital: dimensões, dz e os pixels de todas as camadas.
+    pub fn fingerprint(&self) -> u64 {
+        let mut h = Fingerprint::new();
+        h.write(&self.width.to_le_bytes());
+        h.write(&self.height.to_le_bytes());
+        h.write(&(self.depth as u64).to_le_bytes());
+        h.write(&self.dz.to_le_bytes());
+        for layer in &self.layers {
+            h.write(layer.as_raw());
+        }
+        h.finish()
+    }
+
     // Checa se (x,y,z) cai em obstáculo (pixel preto) — z contínuo é mapeado para slice.
     #[inline]
     pub fn is_obstructed_xyz(&self, x: f64, y: f64, z: f64) -> bool {
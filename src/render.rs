@@ -0,0 +1,101 @@
+use std::io;
+
+use image::{Rgb, RgbImage};
+
+use crate::persist::invalid_data;
+
+// Figura estática de um resultado sobre a imagem do mapa, no lugar das animações de
+// plot/: arestas da árvore em azul, caminho em vermelho, início em verde e objetivo em
+// vermelho. Lê os CSVs no esquema comum (colunas x, y, parent_x, parent_y); em 3D e
+// SE(2) as demais colunas são ignoradas, o que dá a vista de cima.
+
+const TREE_COLOR: Rgb<u8> = Rgb([40, 90, 230]);
+const PATH_COLOR: Rgb<u8> = Rgb([220, 30, 30]);
+const START_COLOR: Rgb<u8> = Rgb([20, 170, 40]);
+
+// Colunas pedidas de um CSV com cabeçalho, linha a linha
+fn read_columns(filename: &str, names: &[&str]) -> io::Result<Vec<Vec<f64>>> {
+    let mut reader = csv::Reader::from_path(filename).map_err(|e| io::Error::other(format!("Não foi possível ler {}: {}", filename, e)))?;
+    let headers = reader.headers()?.clone();
+    let columns: Vec<usize> = names
+        .iter()
+        .map(|name| {
+            headers
+                .iter()
+                .position(|h| h == *name)
+                .ok_or_else(|| invalid_data(format!("Coluna {} não encontrada em {}", name, filename)))
+        })
+        .collect::<io::Result<_>>()?;
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let row = columns
+            .iter()
+            .map(|&c| record[c].trim().parse::<f64>().map_err(|_| invalid_data(format!("Valor inválido em {}: {}", filename, &record[c]))))
+            .collect::<io::Result<Vec<f64>>>()?;
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+fn put(image: &mut RgbImage, x: i64, y: i64, color: Rgb<u8>) {
+    if x >= 0 && y >= 0 && (x as u32) < image.width() && (y as u32) < image.height() {
+        image.put_pixel(x as u32, y as u32, color);
+    }
+}
+
+// Segmento com espessura `width` (quadrado de width x width em cada ponto)
+fn draw_line(image: &mut RgbImage, a: (f64, f64), b: (f64, f64), width: i64, color: Rgb<u8>) {
+    let steps = (b.0 - a.0).abs().max((b.1 - a.1).abs()).ceil().max(1.0) as usize;
+    let half = width / 2;
+    for i in 0..=steps {
+        let t = i as f64 / steps as f64;
+        let (x, y) = ((a.0 + (b.0 - a.0) * t).round() as i64, (a.1 + (b.1 - a.1) * t).round() as i64);
+        for dy in -half..=half {
+            for dx in -half..=half {
+                put(image, x + dx, y + dy, color);
+            }
+        }
+    }
+}
+
+fn draw_dot(image: &mut RgbImage, center: (f64, f64), radius: i64, color: Rgb<u8>) {
+    let (cx, cy) = (center.0.round() as i64, center.1.round() as i64);
+    for dy in -radius..=radius {
+        for dx in -radius..=radius {
+            if dx * dx + dy * dy <= radius * radius {
+                put(image, cx + dx, cy + dy, color);
+            }
+        }
+    }
+}
+
+// Desenha a árvore e/ou o caminho sobre a imagem do mapa e grava o PNG (ou outro
+// formato, pela extensão de `output`)
+pub fn render_map(map_image: &str, nodes_csv: Option<&str>, path_csv: Option<&str>, output: &str) -> io::Result<()> {
+    let mut image = image::open(map_image)
+        .map_err(|e| invalid_data(format!("Não foi possível abrir a imagem do mapa {}: {}", map_image, e)))?
+        .to_rgb8();
+
+    if let Some(filename) = nodes_csv {
+        for row in read_columns(filename, &["x", "y", "parent_x", "parent_y"])? {
+            if !row[2].is_nan() {
+                draw_line(&mut image, (row[0], row[1]), (row[2], row[3]), 1, TREE_COLOR);
+            }
+        }
+    }
+
+    if let Some(filename) = path_csv {
+        let path: Vec<(f64, f64)> = read_columns(filename, &["x", "y"])?.iter().map(|row| (row[0], row[1])).collect();
+        for w in path.windows(2) {
+            draw_line(&mut image, w[0], w[1], 3, PATH_COLOR);
+        }
+        if let (Some(&start), Some(&goal)) = (path.first(), path.last()) {
+            draw_dot(&mut image, start, 6, START_COLOR);
+            draw_dot(&mut image, goal, 6, PATH_COLOR);
+        }
+    }
+
+    image.save(output).map_err(|e| io::Error::other(format!("Não foi possível salvar {}: {}", output, e)))
+}
@@ -0,0 +1,278 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::arm_collision::*;
+use crate::persist::Fingerprint;
+use crate::sampler::*;
+use crate::space::*;
+use crate::structs3d::Point3;
+use crate::tree::*;
+
+// Planejamento no espaço de juntas do braço RoArm-M2 (porte de roarmm2_mov/rrt_ctrl.py
+// e roarmm2_mov/sca_rrt.py). Unidades: metros e radianos.
+
+pub const NUMBER_OF_JOINTS: usize = 4;
+
+// Comprimentos dos elos: base até o ombro, ombro até o cotovelo, cotovelo até a garra
+pub const L1: f64 = 123.06 / 1000.0;
+pub const L2: f64 = 238.71 / 1000.0;
+pub const L3: f64 = 280.15 / 1000.0;
+
+// Desvio angular entre o zero das juntas 2/3 e a geometria dos elos
+pub const JOINT_OFFSET: f64 = 0.12601;
+
+// Limites (mínimo, máximo) de cada junta; a junta 4 (garra) fica fixa
+pub const JOINT_LIMITS: [(f64, f64); NUMBER_OF_JOINTS] = [(-PI, PI), (-PI / 2.0, PI / 2.0), (-PI / 2.0, PI / 2.0), (0.0, 0.0)];
+
+// Configuração do braço: ângulos das 4 juntas (base, ombro, cotovelo, garra)
+pub type Configuration = [f64; NUMBER_OF_JOINTS];
+
+// Posições da base, do ombro, do cotovelo e da garra (fkine_all_joints do sca_rrt.py).
+// O fkine_elbow do rrt_ctrl.py ignora o desvio e troca seno e cosseno da base; aqui o
+// cotovelo segue a mesma cinemática da garra.
+pub fn fkine_all_joints(q: &Configuration) -> [Point3; 4] {
+    let th1 = q[0];
+    let th2 = -q[1] + PI / 2.0 - JOINT_OFFSET;
+    let th3 = -q[2] + JOINT_OFFSET;
+
+    let r_elbow = L2 * th2.cos();
+    let z_elbow = L1 + L2 * th2.sin();
+    let r_tip = r_elbow + L3 * (th2 + th3).cos();
+    let z_tip = z_elbow + L3 * (th2 + th3).sin();
+
+    [
+        Point3 { x: 0.0, y: 0.0, z: 0.0 },
+        Point3 { x: 0.0, y: 0.0, z: L1 },
+        Point3 { x: r_elbow * th1.cos(), y: r_elbow * th1.sin(), z: z_elbow },
+        Point3 { x: r_tip * th1.cos(), y: r_tip * th1.sin(), z: z_tip },
+    ]
+}
+
+// Posição XYZ da garra
+pub fn fkine_hand(q: &Configuration) -> Point3 {
+    fkine_all_joints(q)[3]
+}
+
+// Espaço de juntas limitado por JOINT_LIMITS (amostragem uniforme em cada junta)
+pub fn joint_space() -> EuclideanSpace<Configuration> {
+    EuclideanSpace::new(JOINT_LIMITS.map(|(lo, _)| lo), JOINT_LIMITS.map(|(_, hi)| hi))
+}
+
+// Cena do braço: obstáculos e o chão. O corpo é verificado segundo `mode` (padrão: 9
+// pontos interpolados em cada elo, como o SCA do sca_rrt.py).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArmMap {
+    pub obstacles: Vec<ArmObstacle>,
+    pub mode: ArmCollisionMode,
+}
+
+impl ArmMap {
+    pub fn new(obstacles: Vec<ArmObstacle>) -> Self {
+        ArmMap { obstacles, mode: ArmCollisionMode::default() }
+    }
+
+    // Cena do rrt_ctrl.py: um cilindro e uma caixa baixa
+    pub fn simple() -> Self {
+        ArmMap::new(vec![
+            ArmObstacle::Cylinder { x: 0.17, y: 0.07, radius: 0.025, height: 0.245 },
+            ArmObstacle::Cuboid { x: 0.23, y: 0.037, size_x: 0.06, size_y: 0.23, size_z: 0.045 },
+        ])
+    }
+
+    // Ambiente denso do sca_rrt.py: dois pilares, a caixa no chão e um muro lateral
+    pub fn dense() -> Self {
+        ArmMap::new(vec![
+            ArmObstacle::Cylinder { x: -0.07, y: 0.17, radius: 0.025, height: 0.245 },
+            ArmObstacle::Cuboid { x: -0.037, y: 0.15, size_x: 0.23, size_y: 0.06, size_z: 0.1 },
+            ArmObstacle::Cuboid { x: 0.1, y: 0.10, size_x: 0.05, size_y: 0.30, size_z: 0.20 },
+            ArmObstacle::Cylinder { x: -0.10, y: -0.15, radius: 0.030, height: 0.300 },
+        ])
+    }
+
+    pub fn with_collision_mode(mut self, mode: ArmCollisionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    // Atalho para o modo interpolado com `steps` divisões por elo
+    pub fn with_link_steps(self, steps: usize) -> Self {
+        self.with_collision_mode(ArmCollisionMode::Interpolated { steps: steps.max(1) })
+    }
+
+    pub fn check_collision(&self, q: &Configuration) -> bool {
+        chain_collides(&self.obstacles, &fkine_all_joints(q), self.mode)
+    }
+}
+
+impl ConfigurationMap for ArmMap {
+    type State = Configuration;
+    type Space = EuclideanSpace<Configuration>;
+
+    fn space(&self) -> EuclideanSpace<Configuration> {
+        joint_space()
+    }
+
+    fn is_free(&self, q: &Configuration) -> bool {
+        !self.check_collision(q)
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = Fingerprint::new();
+        for obstacle in &self.obstacles {
+            obstacle.write_fingerprint(&mut hasher);
+        }
+        self.mode.write_fingerprint(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// RRT no espaço de juntas com objetivo no espaço de trabalho: termina quando a garra
+// fica a até goal_threshold do ponto XYZ alvo. Usa a mesma árvore (com índice KD) dos
+// planejadores 2D/3D.
+pub struct ArmPlanner {
+    start: Configuration,
+    goal_xyz: Point3,
+    map: ArmMap,
+    space: EuclideanSpace<Configuration>,
+    sampler: Box<dyn Sampler<ArmMap>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    step_size: f64,
+    goal_threshold: f64,
+    closest_bias: f64,
+    max_iter: u32,
+    collision_steps: i32,
+
+    tree: Tree<Configuration>,
+    path_found: Option<Vec<Configuration>>,
+}
+
+impl ArmPlanner {
+    pub fn new(start: Configuration, goal_xyz: Point3, map: ArmMap, step_size: f64, goal_threshold: f64) -> Self {
+        let space = map.space();
+        ArmPlanner {
+            start,
+            goal_xyz,
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            step_size,
+            goal_threshold,
+            closest_bias: 0.0,
+            max_iter: 20_000,
+            collision_steps: 10,
+            tree: Tree::new(start),
+            path_found: None,
+        }
+    }
+
+    pub fn with_max_iter(mut self, iters: u32) -> Self {
+        self.max_iter = iters;
+        self
+    }
+
+    pub fn with_collision_steps(mut self, steps: i32) -> Self {
+        self.collision_steps = steps.max(1);
+        self
+    }
+
+    // Heurística do rrt_ctrl.py: com essa probabilidade a expansão parte do nó cuja garra
+    // está mais perto do alvo, em vez do vizinho mais próximo da amostra (padrão: 0)
+    pub fn with_closest_bias(mut self, bias: f64) -> Self {
+        self.closest_bias = bias.clamp(0.0, 1.0);
+        self
+    }
+
+    // Troca a fonte de amostras (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<ArmMap> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesma cena reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    pub fn map(&self) -> &ArmMap {
+        &self.map
+    }
+
+    pub fn tree(&self) -> &Tree<Configuration> {
+        &self.tree
+    }
+
+    pub fn path(&self) -> Option<&Vec<Configuration>> {
+        self.path_found.as_ref()
+    }
+
+    // Distância da garra ao alvo numa configuração
+    pub fn goal_distance(&self, q: &Configuration) -> f64 {
+        fkine_hand(q).dist(&self.goal_xyz)
+    }
+
+    pub fn plan(&mut self) -> Option<&Vec<Configuration>> {
+        // Quem chama deve conferir o início antes (ArmMap::check_collision) para dar um erro claro
+        if self.map.check_collision(&self.start) {
+            return None;
+        }
+
+        let mut closest: Option<(usize, f64)> = None;
+        for _ in 0..self.max_iter {
+            let q_rand = self.sampler.sample(&self.map, &self.space, &mut self.rng);
+            let mut nearest_idx = self.tree.nearest(&self.space, &q_rand);
+            if self.rng.r#gen::<f64>() < self.closest_bias
+                && let Some((idx, _)) = closest
+            {
+                nearest_idx = idx;
+            }
+            let q_near = self.tree.nodes[nearest_idx].coord;
+            let q_new = self.space.steer(&q_near, &q_rand, self.step_size);
+
+            if !self.map.is_free(&q_new) || !self.map.is_motion_free(&q_near, &q_new, self.collision_steps) {
+                continue;
+            }
+            let new_idx = self.tree.add_node(&self.space, q_new, nearest_idx);
+            let dist = self.goal_distance(&q_new);
+            if closest.is_none_or(|(_, d)| dist < d) {
+                closest = Some((new_idx, dist));
+            }
+            if dist <= self.goal_threshold {
+                self.path_found = Some(self.tree.reconstruct_path(new_idx));
+                return self.path_found.as_ref();
+            }
+        }
+        None
+    }
+
+    // Caminho da garra no espaço de trabalho
+    pub fn hand_path(&self) -> Option<Vec<Point3>> {
+        self.path_found.as_ref().map(|path| path.iter().map(fkine_hand).collect())
+    }
+
+    // Descarta a árvore e o caminho, recomeçando da configuração inicial
+    // (e da mesma sequência aleatória, se houver semente)
+    pub fn reset(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.tree = Tree::new(self.start);
+        self.path_found = None;
+    }
+
+    pub fn save_nodes_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        write_nodes_csv(filename, &[&self.tree])
+    }
+
+    // Caminho em juntas (q0..q3)
+    pub fn save_path_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        match &self.path_found {
+            Some(path) => write_path_csv(filename, path),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Nenhum caminho final para salvar.")),
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::readmap::OccupancyMap;
+use rand::rngs::StdRng;
+
+use crate::sampler::*;
+use crate::space::*;
+use crate::tree::*;
+
+// Resultado de uma tentativa de extensão de uma árvore em direção a um ponto
+enum Extend {
+    Trapped,
+    Advanced(usize),
+    Reached(usize),
+}
+
+// RRT-Connect: duas árvores (uma no início, outra no objetivo) crescendo uma
+// em direção à outra com a extensão gulosa "connect".
+pub struct RRTConnectPlanner<M: ConfigurationMap = OccupancyMap> {
+    start: M::State,
+    goal: M::State,
+    map: M,
+    space: M::Space,
+    sampler: Box<dyn Sampler<M>>,
+    seed: Option<u64>,
+    rng: StdRng,
+    step_size: f64,
+    max_iter: u32,
+    num_collision_check_steps: i32,
+
+    start_tree: Tree<M::State>,
+    goal_tree: Tree<M::State>,
+    path_found: Option<Vec<M::State>>,
+}
+
+impl<M: ConfigurationMap> RRTConnectPlanner<M> {
+    pub fn new(start: M::State, goal: M::State, map: M, step_size: f64, max_iter: u32, num_collision_check_steps: i32) -> Self {
+        // Com passo nulo o connect nunca alcança o alvo nem é barrado, e fica em laço
+        assert!(step_size > 0.0, "O passo do RRT-Connect deve ser positivo");
+        let space = map.space();
+        RRTConnectPlanner {
+            start,
+            goal,
+            map,
+            space,
+            sampler: Box::new(UniformSampler),
+            seed: None,
+            rng: planner_rng(None),
+            step_size,
+            max_iter,
+            num_collision_check_steps,
+            start_tree: Tree::new(start),
+            goal_tree: Tree::new(goal),
+            path_found: None,
+        }
+    }
+
+    // Troca a fonte de amostras (padrão: UniformSampler)
+    pub fn with_sampler(mut self, sampler: impl Sampler<M> + 'static) -> Self {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    // Nós nas duas árvores
+    pub fn node_count(&self) -> usize {
+        self.start_tree.len() + self.goal_tree.len()
+    }
+
+    // Salva os nós das duas árvores no mesmo arquivo, no esquema comum dos planejadores
+    pub fn save_nodes_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        write_nodes_csv(filename, &[&self.start_tree, &self.goal_tree])
+    }
+
+    // Salva o caminho final em um arquivo CSV
+    pub fn save_path_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        match &self.path_found {
+            Some(path) => write_path_csv(filename, path),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Nenhum caminho final para salvar.")),
+        }
+    }
+
+    // Dá um passo de tamanho step_size da árvore em direção ao alvo
+    fn extend(&self, tree: &mut Tree<M::State>, target: &M::State) -> Extend {
+        let nearest_idx = tree.nearest(&self.space, target);
+        let nearest_coord = tree.nodes[nearest_idx].coord;
+
+        let q_new = self.space.steer(&nearest_coord, target, self.step_size);
+        let reached = q_new == *target;
+
+        if !self.map.is_motion_free(&nearest_coord, &q_new, self.num_collision_check_steps) {
+            return Extend::Trapped;
+        }
+        let new_idx = tree.add_node(&self.space, q_new, nearest_idx);
+        if reached { Extend::Reached(new_idx) } else { Extend::Advanced(new_idx) }
+    }
+
+    // Extensão gulosa: avança até alcançar o alvo ou bater em um obstáculo
+    fn connect(&self, tree: &mut Tree<M::State>, target: &M::State) -> Extend {
+        loop {
+            match self.extend(tree, target) {
+                Extend::Advanced(_) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    pub fn plan(&mut self) -> Option<&Vec<M::State>> {
+        let mut grow_start_tree = true;
+        // As árvores saem do planejador durante a iteração para que extend/connect possam usar &self
+        let mut start_tree = std::mem::replace(&mut self.start_tree, Tree::new(self.start));
+        let mut goal_tree = std::mem::replace(&mut self.goal_tree, Tree::new(self.goal));
+        let mut junction = None;
+
+        for _ in 0..self.max_iter {
+            let q_rand = self.sampler.sample(&self.map, &self.space, &mut self.rng);
+
+            let (tree_a, tree_b) = if grow_start_tree {
+                (&mut start_tree, &mut goal_tree)
+            } else {
+                (&mut goal_tree, &mut start_tree)
+            };
+
+            let new_idx_a = match self.extend(tree_a, &q_rand) {
+                Extend::Trapped => {
+                    grow_start_tree = !grow_start_tree;
+                    continue;
+                }
+                Extend::Advanced(idx) | Extend::Reached(idx) => idx,
+            };
+            let q_new = tree_a.nodes[new_idx_a].coord;
+
+            if let Extend::Reached(new_idx_b) = self.connect(tree_b, &q_new) {
+                junction = Some(if grow_start_tree { (new_idx_a, new_idx_b) } else { (new_idx_b, new_idx_a) });
+                break;
+            }
+
+            grow_start_tree = !grow_start_tree;
+        }
+
+        self.start_tree = start_tree;
+        self.goal_tree = goal_tree;
+
+        let (start_idx, goal_idx) = junction?;
+
+        // Caminho do início até a junção, depois da junção até o objetivo
+        let mut path = self.start_tree.reconstruct_path(start_idx);
+        let mut to_goal = self.goal_tree.reconstruct_path(goal_idx);
+        to_goal.reverse();
+        path.extend(to_goal.into_iter().skip(1));
+
+        self.path_found = Some(path);
+        self.path_found.as_ref()
+    }
+
+    pub fn path_cost(&self) -> Option<f64> {
+        self.path_found.as_ref().map(|path| path_length(&self.space, path))
+    }
+
+    pub fn reset(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.start_tree = Tree::new(self.start);
+        self.goal_tree = Tree::new(self.goal);
+        self.path_found = None;
+    }
+}
@@ -0,0 +1,346 @@
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+use crate::space::*;
+
+// Fonte de amostras dos planejadores. Recebe o mapa além do espaço para que
+// amostradores que olham os obstáculos também caibam na mesma interface, e o
+// gerador do planejador para que a semente reproduza a mesma sequência.
+pub trait Sampler<M: ConfigurationMap> {
+    fn sample(&mut self, map: &M, space: &M::Space, rng: &mut dyn RngCore) -> M::State;
+}
+
+// Gerador dos planejadores: com semente é reprodutível (mesma sequência em qualquer
+// execução), sem semente é inicializado pela entropia do sistema
+pub(crate) fn planner_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+// Amostra do sampler ou, com probabilidade goal_bias, o próprio objetivo
+pub(crate) fn goal_biased<M: ConfigurationMap>(
+    sampler: &mut dyn Sampler<M>,
+    map: &M,
+    space: &M::Space,
+    goal: &M::State,
+    goal_bias: f64,
+    rng: &mut dyn RngCore,
+) -> M::State {
+    if goal_bias > 0.0 && rng.r#gen::<f64>() < goal_bias {
+        *goal
+    } else {
+        sampler.sample(map, space, rng)
+    }
+}
+
+// Amostragem uniforme do espaço (padrão de todos os planejadores)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct UniformSampler;
+
+impl<M: ConfigurationMap> Sampler<M> for UniformSampler {
+    fn sample(&mut self, _map: &M, space: &M::Space, rng: &mut dyn RngCore) -> M::State {
+        space.sample_uniform(rng)
+    }
+}
+
+// Sequência de Halton: inverso radical do índice na base do i-ésimo primo para cada eixo
+#[derive(Clone, Debug)]
+pub struct HaltonSampler {
+    index: u64,
+}
+
+impl Default for HaltonSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HaltonSampler {
+    pub fn new() -> Self {
+        // O índice 0 daria a origem do cubo; a sequência começa em 1
+        HaltonSampler { index: 1 }
+    }
+
+    pub fn next_unit(&mut self, dim: usize) -> Vec<f64> {
+        let u = first_primes(dim).into_iter().map(|base| radical_inverse(self.index, base)).collect();
+        self.index += 1;
+        u
+    }
+}
+
+impl<M: ConfigurationMap> Sampler<M> for HaltonSampler {
+    fn sample(&mut self, _map: &M, space: &M::Space, _rng: &mut dyn RngCore) -> M::State {
+        space.unit_to_state(&self.next_unit(M::State::DIM))
+    }
+}
+
+fn radical_inverse(mut i: u64, base: u64) -> f64 {
+    let mut result = 0.0;
+    let mut f = 1.0 / base as f64;
+    while i > 0 {
+        result += f * (i % base) as f64;
+        i /= base;
+        f /= base as f64;
+    }
+    result
+}
+
+fn first_primes(n: usize) -> Vec<u64> {
+    let mut primes: Vec<u64> = Vec::with_capacity(n);
+    let mut candidate = 2;
+    while primes.len() < n {
+        if primes.iter().take_while(|&&p| p * p <= candidate).all(|&p| candidate % p != 0) {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+// Números de direção de Joe e Kuo (grau s, coeficientes a, valores iniciais m)
+// para os eixos 2 em diante; o primeiro eixo é a sequência de van der Corput
+const SOBOL_DIRECTIONS: [(u32, u32, &[u32]); 11] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+];
+
+// Sequência de Sobol (construção por código de Gray), até 12 dimensões
+#[derive(Clone, Debug, Default)]
+pub struct SobolSampler {
+    index: u32,
+    state: Vec<u32>,
+    directions: Vec<[u32; 32]>,
+}
+
+impl SobolSampler {
+    pub const MAX_DIM: usize = SOBOL_DIRECTIONS.len() + 1;
+
+    pub fn new() -> Self {
+        SobolSampler::default()
+    }
+
+    fn init(&mut self, dim: usize) {
+        assert!(dim <= Self::MAX_DIM, "SobolSampler suporta até {} dimensões", Self::MAX_DIM);
+        self.directions = (0..dim)
+            .map(|axis| {
+                let mut v = [0u32; 32];
+                if axis == 0 {
+                    for (k, vk) in v.iter_mut().enumerate() {
+                        *vk = 1 << (31 - k);
+                    }
+                    return v;
+                }
+                let (s, a, m) = SOBOL_DIRECTIONS[axis - 1];
+                let s = s as usize;
+                for k in 0..32 {
+                    v[k] = if k < s {
+                        m[k] << (31 - k)
+                    } else {
+                        let mut vk = v[k - s] ^ (v[k - s] >> s);
+                        for l in 1..s {
+                            if (a >> (s - 1 - l)) & 1 == 1 {
+                                vk ^= v[k - l];
+                            }
+                        }
+                        vk
+                    };
+                }
+                v
+            })
+            .collect();
+        self.state = vec![0; dim];
+        self.index = 0;
+    }
+
+    pub fn next_unit(&mut self, dim: usize) -> Vec<f64> {
+        if self.directions.len() != dim {
+            self.init(dim);
+        }
+        // Pula o ponto 0 (origem): cada chamada avança um ponto antes de devolver
+        let c = self.index.trailing_ones() as usize;
+        self.index += 1;
+        for (x, v) in self.state.iter_mut().zip(&self.directions) {
+            *x ^= v[c];
+        }
+        self.state.iter().map(|&x| x as f64 / 4_294_967_296.0).collect()
+    }
+}
+
+impl<M: ConfigurationMap> Sampler<M> for SobolSampler {
+    fn sample(&mut self, _map: &M, space: &M::Space, _rng: &mut dyn RngCore) -> M::State {
+        space.unit_to_state(&self.next_unit(M::State::DIM))
+    }
+}
+
+// Vetor gerador do reticulado (base 2), até 10 dimensões
+const LATTICE_GENERATOR: [u64; 10] = [1, 182_667, 469_891, 498_753, 110_745, 446_247, 250_185, 118_627, 245_333, 283_199];
+
+// Reticulado de posto 1 extensível: x_i = frac(phi_2(i) * z), com phi_2 o inverso
+// radical na base 2. Os primeiros 2^m pontos formam sempre um reticulado completo,
+// então a cobertura é uniforme em qualquer número de amostras potência de 2.
+#[derive(Clone, Debug)]
+pub struct LatticeSampler {
+    index: u32,
+}
+
+impl Default for LatticeSampler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LatticeSampler {
+    pub const MAX_DIM: usize = LATTICE_GENERATOR.len();
+
+    pub fn new() -> Self {
+        LatticeSampler { index: 1 }
+    }
+
+    pub fn next_unit(&mut self, dim: usize) -> Vec<f64> {
+        assert!(dim <= Self::MAX_DIM, "LatticeSampler suporta até {} dimensões", Self::MAX_DIM);
+        let phi = self.index.reverse_bits() as u64;
+        self.index += 1;
+        LATTICE_GENERATOR[..dim]
+            .iter()
+            .map(|&z| ((phi * z) & 0xFFFF_FFFF) as f64 / 4_294_967_296.0)
+            .collect()
+    }
+}
+
+impl<M: ConfigurationMap> Sampler<M> for LatticeSampler {
+    fn sample(&mut self, _map: &M, space: &M::Space, _rng: &mut dyn RngCore) -> M::State {
+        space.unit_to_state(&self.next_unit(M::State::DIM))
+    }
+}
+
+// Tentativas dos amostradores com rejeição antes de cair na amostra uniforme
+pub(crate) const MAX_ATTEMPTS: usize = 1000;
+
+// Livre e dentro dos limites do espaço (fora dos limites conta como obstáculo)
+fn is_valid<M: ConfigurationMap>(map: &M, space: &M::Space, q: &M::State) -> bool {
+    let (lower, upper) = space.bounds();
+    (0..M::State::DIM).all(|axis| q.coord(axis) >= lower.coord(axis) && q.coord(axis) <= upper.coord(axis)) && map.is_free(q)
+}
+
+// Estado deslocado de q por `scale * delta`
+fn offset<S: State>(q: &S, delta: &[f64], scale: f64) -> S {
+    let coords: Vec<f64> = (0..S::DIM).map(|axis| q.coord(axis) + scale * delta[axis]).collect();
+    S::from_coords(&coords)
+}
+
+// Amostragem gaussiana: sorteia um par (uniforme, uniforme + ruído gaussiano de desvio
+// sigma) e fica com o estado livre quando só um dos dois é livre. As amostras se
+// concentram a cerca de sigma das bordas dos obstáculos.
+#[derive(Clone, Copy, Debug)]
+pub struct GaussianSampler {
+    pub sigma: f64,
+}
+
+impl GaussianSampler {
+    pub fn new(sigma: f64) -> Self {
+        GaussianSampler { sigma }
+    }
+}
+
+impl<M: ConfigurationMap> Sampler<M> for GaussianSampler {
+    fn sample(&mut self, map: &M, space: &M::Space, rng: &mut dyn RngCore) -> M::State {
+        for _ in 0..MAX_ATTEMPTS {
+            let q1 = space.sample_uniform(rng);
+            let noise: Vec<f64> = (0..M::State::DIM).map(|_| standard_normal(rng)).collect();
+            let q2 = offset(&q1, &noise, self.sigma);
+            match (is_valid(map, space, &q1), is_valid(map, space, &q2)) {
+                (true, false) => return q1,
+                (false, true) => return q2,
+                _ => {}
+            }
+        }
+        space.sample_uniform(rng)
+    }
+}
+
+// Teste da ponte: dois estados em colisão a cerca de sigma um do outro cujo ponto
+// médio é livre. Favorece passagens estreitas, onde a "ponte" atravessa o vão.
+#[derive(Clone, Copy, Debug)]
+pub struct BridgeSampler {
+    pub sigma: f64,
+}
+
+impl BridgeSampler {
+    pub fn new(sigma: f64) -> Self {
+        BridgeSampler { sigma }
+    }
+}
+
+impl<M: ConfigurationMap> Sampler<M> for BridgeSampler {
+    fn sample(&mut self, map: &M, space: &M::Space, rng: &mut dyn RngCore) -> M::State {
+        for _ in 0..MAX_ATTEMPTS {
+            let q1 = space.sample_uniform(rng);
+            if is_valid(map, space, &q1) {
+                continue;
+            }
+            let noise: Vec<f64> = (0..M::State::DIM).map(|_| standard_normal(rng)).collect();
+            let q2 = offset(&q1, &noise, self.sigma);
+            if is_valid(map, space, &q2) {
+                continue;
+            }
+            let mid = space.interpolate(&q1, &q2, 0.5);
+            if is_valid(map, space, &mid) {
+                return mid;
+            }
+        }
+        space.sample_uniform(rng)
+    }
+}
+
+// Amostragem baseada em obstáculos (OBPRM): parte de um estado em colisão e anda em
+// uma direção aleatória, em passos de step, até o primeiro estado livre, que fica
+// encostado na borda do obstáculo
+#[derive(Clone, Copy, Debug)]
+pub struct ObstacleBasedSampler {
+    step: f64,
+}
+
+impl ObstacleBasedSampler {
+    pub fn new(step: f64) -> Self {
+        assert!(step > 0.0, "O passo do ObstacleBasedSampler deve ser positivo");
+        ObstacleBasedSampler { step }
+    }
+}
+
+impl<M: ConfigurationMap> Sampler<M> for ObstacleBasedSampler {
+    fn sample(&mut self, map: &M, space: &M::Space, rng: &mut dyn RngCore) -> M::State {
+        let (lower, upper) = space.bounds();
+        let in_bounds = |q: &M::State| (0..M::State::DIM).all(|axis| q.coord(axis) >= lower.coord(axis) && q.coord(axis) <= upper.coord(axis));
+
+        for _ in 0..MAX_ATTEMPTS {
+            let q = space.sample_uniform(rng);
+            if map.is_free(&q) {
+                continue;
+            }
+            let dir = random_direction(rng, M::State::DIM);
+            let mut k = 1.0;
+            loop {
+                let p = offset(&q, &dir, k * self.step);
+                if !in_bounds(&p) {
+                    break;
+                }
+                if map.is_free(&p) {
+                    return p;
+                }
+                k += 1.0;
+            }
+        }
+        space.sample_uniform(rng)
+    }
+}
@@ -0,0 +1,827 @@
+use std::io;
+use std::path::Path;
+use std::time::Instant;
+
+use serde::Deserialize;
+
+use crate::arm_collision::*;
+use crate::bit_star::BITStarPlanner;
+use crate::dynamics::*;
+use crate::est::ESTPlanner;
+use crate::fmt_star::FMTPlanner;
+use crate::footprint::RobotRadius;
+use crate::geometry::*;
+use crate::kinodynamic::KinodynamicPlanner;
+use crate::kpiece::KPIECEPlanner;
+use crate::persist::invalid_data;
+use crate::planner::TreePlanner;
+use crate::prm::PRMPlanner;
+use crate::readmap::OccupancyMap;
+use crate::readmap3d::OccupancyMap3D;
+use crate::roarm::*;
+use crate::rrt_connect::RRTConnectPlanner;
+use crate::se2::*;
+use crate::space::*;
+use crate::sst::SSTPlanner;
+use crate::structs3d::Point3;
+use crate::tree::{path_length, write_path_csv};
+
+// Arquivo de cena (TOML ou JSON): fonte do mapa, início, objetivo, modelo do robô,
+// parâmetros do planejador e arquivos de saída. Exemplos em scenes/.
+//
+//   start = [50.0, 50.0]
+//   goal = [700.0, 500.0]
+//
+//   [map]
+//   type = "image"
+//   path = "data/map.jpg"
+//
+//   [planner]
+//   algorithm = "rrt_star"
+//   step_size = 15.0
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scene {
+    pub map: MapSource,
+    // Coordenadas do estado inicial e do objetivo (para o braço: juntas e ponto XYZ da garra)
+    pub start: Vec<f64>,
+    pub goal: Vec<f64>,
+    #[serde(default)]
+    pub robot: RobotModel,
+    #[serde(default)]
+    pub planner: PlannerParams,
+    #[serde(default)]
+    pub output: OutputFiles,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum MapSource {
+    // Mapa 2D a partir de uma imagem (preto = obstáculo)
+    Image { path: String },
+    // Mapa 3D com a mesma imagem repetida em `depth` camadas
+    Replicated { path: String, depth: usize, dz: f64 },
+    // Mapa 3D com uma imagem por camada
+    Layers { paths: Vec<String>, dz: f64 },
+    // Mundo geométrico 3D dentro da caixa [lower, upper]
+    Primitives { lower: [f64; 3], upper: [f64; 3], obstacles: Vec<PrimitiveSpec> },
+    // Bancada do braço, no formato do OBSTACLES_LIST dos scripts em Python
+    Arm { obstacles: Vec<ArmObstacleSpec> },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum PrimitiveSpec {
+    Aabb { min: [f64; 3], max: [f64; 3] },
+    Obb {
+        center: [f64; 3],
+        size: [f64; 3],
+        #[serde(default)]
+        roll: f64,
+        #[serde(default)]
+        pitch: f64,
+        #[serde(default)]
+        yaw: f64,
+    },
+    Cylinder { base: [f64; 3], top: [f64; 3], radius: f64 },
+    Sphere { center: [f64; 3], radius: f64 },
+    Capsule { a: [f64; 3], b: [f64; 3], radius: f64 },
+}
+
+// Aceita os dicionários do Python como estão: chaves extras ("name", "z") são ignoradas
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArmObstacleSpec {
+    Cylinder { x: f64, y: f64, r: f64, h: f64 },
+    Box { x: f64, y: f64, size_x: f64, size_y: f64, size_z: f64 },
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum RobotModel {
+    // Robô pontual (padrão)
+    #[default]
+    Point,
+    // Disco/esfera: infla os mapas raster (raio em pixels) ou vira folga no mundo geométrico
+    Disk { radius: f64 },
+    // Contorno poligonal no plano (estado x, y, theta); só em mapas de imagem
+    Polygon { vertices: Vec<[f64; 2]> },
+    Rectangle { length: f64, width: f64 },
+    // Braço RoArm-M2: colisão só da garra, interpolada nos elos ou por cápsulas
+    Arm {
+        #[serde(default)]
+        collision: ArmCollision,
+        #[serde(default = "default_link_steps")]
+        link_steps: usize,
+        #[serde(default)]
+        link_radius: f64,
+    },
+}
+
+// Ver ArmCollisionMode
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArmCollision {
+    Hand,
+    #[default]
+    Interpolated,
+    Capsule,
+}
+
+fn default_link_steps() -> usize {
+    8
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Algorithm {
+    #[default]
+    Rrt,
+    RrtStar,
+    InformedRrtStar,
+    RrtConnect,
+    Prm,
+    LazyPrm,
+    FmtStar,
+    BitStar,
+    Est,
+    Kpiece,
+    // Cinodinâmicos: usam o modelo dinâmico em vez de ligar estados por retas
+    KinodynamicRrt,
+    Sst,
+}
+
+impl Algorithm {
+    pub fn is_kinodynamic(&self) -> bool {
+        matches!(self, Algorithm::KinodynamicRrt | Algorithm::Sst)
+    }
+}
+
+// Modelo dinâmico dos planejadores cinodinâmicos, em unidades do mapa por segundo
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DynamicsModel {
+    // Aceleração como controle; estado [posição..., velocidade...] (ver DoubleIntegrator)
+    DoubleIntegrator { max_speed: f64, max_accel: f64 },
+    // Velocidade linear e angular como controle; estado x, y, theta, só em mapas de imagem 2D
+    Unicycle {
+        #[serde(default)]
+        min_speed: f64,
+        max_speed: f64,
+        max_turn_rate: f64,
+    },
+}
+
+impl Default for DynamicsModel {
+    fn default() -> Self {
+        DynamicsModel::DoubleIntegrator { max_speed: 50.0, max_accel: 25.0 }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlannerParams {
+    pub algorithm: Algorithm,
+    pub step_size: f64,
+    pub goal_radius: f64,
+    pub goal_bias: f64,
+    pub max_iter: u32,
+    pub collision_steps: i32,
+    pub seed: Option<u64>,
+    // Só para o braço: heurística do nó mais perto do alvo (ver ArmPlanner::with_closest_bias)
+    pub closest_bias: f64,
+    // PRM, Lazy PRM e FMT*: amostras do roadmap ou do lote; BIT*: amostras por lote
+    pub num_samples: usize,
+    // Só para o BIT*
+    pub max_batches: usize,
+    // Só para kinodynamic_rrt e sst: modelo e passo de integração em segundos
+    pub dynamics: DynamicsModel,
+    pub dt: f64,
+}
+
+impl Default for PlannerParams {
+    fn default() -> Self {
+        PlannerParams {
+            algorithm: Algorithm::Rrt,
+            step_size: 15.0,
+            goal_radius: 10.0,
+            goal_bias: 0.05,
+            max_iter: 20_000,
+            collision_steps: 20,
+            seed: None,
+            closest_bias: 0.0,
+            num_samples: 1000,
+            max_batches: 20,
+            dynamics: DynamicsModel::default(),
+            dt: 0.1,
+        }
+    }
+}
+
+// CSVs gravados depois do planejamento (nenhum por padrão)
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OutputFiles {
+    pub nodes: Option<String>,
+    pub path: Option<String>,
+}
+
+fn point3(c: &[f64; 3]) -> Point3 {
+    Point3 { x: c[0], y: c[1], z: c[2] }
+}
+
+fn invalid_input(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg.into())
+}
+
+// Dimensão finita e estritamente positiva
+fn check_positive(name: &str, value: f64) -> io::Result<()> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(invalid_input(format!("{} deve ser positivo (recebido {})", name, value)))
+    }
+}
+
+impl PrimitiveSpec {
+    // Recusa sólidos degenerados: dimensões não positivas ou cilindro sem eixo
+    pub fn to_primitive(&self) -> io::Result<Primitive> {
+        match self {
+            PrimitiveSpec::Aabb { min, max } => {
+                for axis in 0..3 {
+                    check_positive("O tamanho da caixa (max - min)", max[axis] - min[axis])?;
+                }
+            }
+            PrimitiveSpec::Obb { size, .. } => {
+                for s in size {
+                    check_positive("O tamanho da caixa orientada", *s)?;
+                }
+            }
+            PrimitiveSpec::Cylinder { base, top, radius } => {
+                check_positive("O raio do cilindro", *radius)?;
+                if base == top {
+                    return Err(invalid_input(format!("Cilindro com base e topo iguais: {:?}", base)));
+                }
+            }
+            PrimitiveSpec::Sphere { radius, .. } => check_positive("O raio da esfera", *radius)?,
+            PrimitiveSpec::Capsule { radius, .. } => check_positive("O raio da cápsula", *radius)?,
+        }
+        Ok(match self {
+            PrimitiveSpec::Aabb { min, max } => Primitive::Aabb { min: point3(min), max: point3(max) },
+            PrimitiveSpec::Obb { center, size, roll, pitch, yaw } => {
+                Primitive::oriented_box(point3(center), point3(size), *roll, *pitch, *yaw)
+            }
+            PrimitiveSpec::Cylinder { base, top, radius } => {
+                Primitive::Cylinder { base: point3(base), top: point3(top), radius: *radius }
+            }
+            PrimitiveSpec::Sphere { center, radius } => Primitive::Sphere { center: point3(center), radius: *radius },
+            PrimitiveSpec::Capsule { a, b, radius } => Primitive::Capsule { a: point3(a), b: point3(b), radius: *radius },
+        })
+    }
+}
+
+impl ArmObstacleSpec {
+    pub fn from_obstacle(obstacle: &ArmObstacle) -> Self {
+        match *obstacle {
+            ArmObstacle::Cylinder { x, y, radius, height } => ArmObstacleSpec::Cylinder { x, y, r: radius, h: height },
+            ArmObstacle::Cuboid { x, y, size_x, size_y, size_z } => ArmObstacleSpec::Box { x, y, size_x, size_y, size_z },
+        }
+    }
+
+    pub fn check(&self) -> io::Result<()> {
+        match *self {
+            ArmObstacleSpec::Cylinder { r, h, .. } => {
+                check_positive("O raio do cilindro", r)?;
+                check_positive("A altura do cilindro", h)
+            }
+            ArmObstacleSpec::Box { size_x, size_y, size_z, .. } => {
+                check_positive("size_x da caixa", size_x)?;
+                check_positive("size_y da caixa", size_y)?;
+                check_positive("size_z da caixa", size_z)
+            }
+        }
+    }
+
+    pub fn to_obstacle(&self) -> ArmObstacle {
+        match *self {
+            ArmObstacleSpec::Cylinder { x, y, r, h } => ArmObstacle::Cylinder { x, y, radius: r, height: h },
+            ArmObstacleSpec::Box { x, y, size_x, size_y, size_z } => ArmObstacle::Cuboid { x, y, size_x, size_y, size_z },
+        }
+    }
+}
+
+// Estado a partir das coordenadas da cena, conferindo a dimensão
+fn state_from<S: State>(name: &str, coords: &[f64]) -> io::Result<S> {
+    if coords.len() == S::DIM {
+        Ok(S::from_coords(coords))
+    } else {
+        Err(invalid_data(format!("{} tem {} coordenadas, esperado {}", name, coords.len(), S::DIM)))
+    }
+}
+
+fn check_file(path: &str) -> io::Result<()> {
+    if Path::new(path).is_file() {
+        Ok(())
+    } else {
+        Err(io::Error::new(io::ErrorKind::NotFound, format!("Arquivo do mapa não encontrado: {}", path)))
+    }
+}
+
+impl Scene {
+    // Lê a cena; o formato vem da extensão (.toml ou .json)
+    pub fn load(filename: &str) -> io::Result<Scene> {
+        let text = std::fs::read_to_string(filename)?;
+        match Path::new(filename).extension().and_then(|e| e.to_str()) {
+            Some("toml") => Scene::from_toml_str(&text),
+            Some("json") => Scene::from_json_str(&text),
+            _ => Err(invalid_data(format!("Extensão de cena desconhecida (use .toml ou .json): {}", filename))),
+        }
+    }
+
+    pub fn from_toml_str(text: &str) -> io::Result<Scene> {
+        let scene: Scene = toml::from_str(text).map_err(|e| invalid_data(format!("Cena TOML inválida: {}", e)))?;
+        scene.validate()?;
+        Ok(scene)
+    }
+
+    pub fn from_json_str(text: &str) -> io::Result<Scene> {
+        let scene: Scene = serde_json::from_str(text).map_err(|e| invalid_data(format!("Cena JSON inválida: {}", e)))?;
+        scene.validate()?;
+        Ok(scene)
+    }
+
+    // Parâmetros fora da faixa fariam os planejadores travar (step_size = 0 no RRT-Connect)
+    // ou dividir por zero, então são recusados antes de montar o mapa. O robô também
+    // precisa combinar com o mapa: nenhum modelo é trocado em silêncio por um ponto.
+    pub fn validate(&self) -> io::Result<()> {
+        let invalid = |msg: String| Err(invalid_input(msg));
+        self.validate_robot()?;
+        let p = &self.planner;
+        if !(p.step_size.is_finite() && p.step_size > 0.0) {
+            return invalid(format!("step_size deve ser positivo (recebido {})", p.step_size));
+        }
+        if !(p.goal_radius.is_finite() && p.goal_radius >= 0.0) {
+            return invalid(format!("goal_radius não pode ser negativo (recebido {})", p.goal_radius));
+        }
+        if !(0.0..=1.0).contains(&p.goal_bias) {
+            return invalid(format!("goal_bias deve estar entre 0 e 1 (recebido {})", p.goal_bias));
+        }
+        if p.collision_steps < 1 {
+            return invalid(format!("collision_steps deve ser pelo menos 1 (recebido {})", p.collision_steps));
+        }
+        if p.num_samples == 0 || p.max_batches == 0 {
+            return invalid("num_samples e max_batches devem ser pelo menos 1".to_string());
+        }
+        self.validate_algorithm()?;
+        match &self.map {
+            MapSource::Replicated { depth, dz, .. } => {
+                if *depth == 0 {
+                    return invalid("depth deve ser pelo menos 1".to_string());
+                }
+                if !(dz.is_finite() && *dz > 0.0) {
+                    return invalid(format!("dz deve ser positivo (recebido {})", dz));
+                }
+            }
+            MapSource::Layers { dz, .. } if !(dz.is_finite() && *dz > 0.0) => {
+                return invalid(format!("dz deve ser positivo (recebido {})", dz));
+            }
+            MapSource::Primitives { lower, upper, obstacles } => {
+                for axis in 0..3 {
+                    check_positive("O tamanho do mundo (upper - lower)", upper[axis] - lower[axis])?;
+                }
+                for obstacle in obstacles {
+                    obstacle.to_primitive()?;
+                }
+            }
+            MapSource::Arm { obstacles } => {
+                for obstacle in obstacles {
+                    obstacle.check()?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // Planejadores que o mapa, o robô e as saídas pedidas permitem, e o modelo dinâmico
+    fn validate_algorithm(&self) -> io::Result<()> {
+        let p = &self.planner;
+        if matches!(self.map, MapSource::Arm { .. }) && p.algorithm != Algorithm::Rrt {
+            return Err(invalid_input("O braço só tem o planejador rrt"));
+        }
+        if self.output.nodes.is_some() && matches!(p.algorithm, Algorithm::FmtStar | Algorithm::BitStar) {
+            return Err(invalid_input("fmt_star e bit_star não gravam os nós (tire output.nodes / --nodes)"));
+        }
+        if !p.algorithm.is_kinodynamic() {
+            return Ok(());
+        }
+        check_positive("dt", p.dt)?;
+        match p.dynamics {
+            DynamicsModel::DoubleIntegrator { max_speed, max_accel } => {
+                check_positive("max_speed", max_speed)?;
+                check_positive("max_accel", max_accel)
+            }
+            DynamicsModel::Unicycle { min_speed, max_speed, max_turn_rate } => {
+                check_positive("max_speed", max_speed)?;
+                check_positive("max_turn_rate", max_turn_rate)?;
+                if !(min_speed.is_finite() && min_speed <= max_speed) {
+                    return Err(invalid_input(format!("min_speed deve ser no máximo max_speed (recebido {})", min_speed)));
+                }
+                if !matches!(self.map, MapSource::Image { .. }) || self.footprint()?.is_some() {
+                    return Err(invalid_input("O uniciclo só vale em mapas de imagem 2D, com robô point ou disk"));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // Combinações de robô e mapa que os planejadores sabem tratar, e as dimensões do robô
+    fn validate_robot(&self) -> io::Result<()> {
+        let map = match self.map {
+            MapSource::Image { .. } => "image",
+            MapSource::Replicated { .. } => "replicated",
+            MapSource::Layers { .. } => "layers",
+            MapSource::Primitives { .. } => "primitives",
+            MapSource::Arm { .. } => "arm",
+        };
+        let (robot, supported) = match &self.robot {
+            // Na bancada, o robô padrão é o próprio braço com a colisão padrão
+            RobotModel::Point => ("point", true),
+            RobotModel::Disk { radius } => {
+                if !(radius.is_finite() && *radius >= 0.0) {
+                    return Err(invalid_input(format!("O raio do disco não pode ser negativo (recebido {})", radius)));
+                }
+                ("disk", map != "arm")
+            }
+            RobotModel::Polygon { .. } => {
+                self.footprint()?;
+                ("polygon", map == "image")
+            }
+            RobotModel::Rectangle { .. } => {
+                self.footprint()?;
+                ("rectangle", map == "image")
+            }
+            RobotModel::Arm { link_steps, link_radius, .. } => {
+                if *link_steps == 0 {
+                    return Err(invalid_input("link_steps deve ser pelo menos 1"));
+                }
+                if !(link_radius.is_finite() && *link_radius >= 0.0) {
+                    return Err(invalid_input(format!("link_radius não pode ser negativo (recebido {})", link_radius)));
+                }
+                ("arm", map == "arm")
+            }
+        };
+        if supported {
+            Ok(())
+        } else {
+            Err(invalid_input(format!(
+                "O robô \"{}\" não é suportado no mapa \"{}\" (image: point, disk, polygon, rectangle; \
+                 replicated, layers e primitives: point, disk; arm: arm)",
+                robot, map
+            )))
+        }
+    }
+
+    // Início e objetivo como estados do mapa, conferindo o número de coordenadas
+    pub fn endpoints<S: State>(&self) -> io::Result<(S, S)> {
+        Ok((state_from("start", &self.start)?, state_from("goal", &self.goal)?))
+    }
+
+    pub fn occupancy_map(&self) -> io::Result<OccupancyMap> {
+        let MapSource::Image { path } = &self.map else {
+            return Err(invalid_data("A cena não usa um mapa de imagem 2D"));
+        };
+        check_file(path)?;
+        let map = OccupancyMap::try_new(path)?;
+        Ok(match self.robot {
+            RobotModel::Disk { radius } => map.inflate(RobotRadius::Pixels(radius)),
+            _ => map,
+        })
+    }
+
+    pub fn occupancy_map_3d(&self) -> io::Result<OccupancyMap3D> {
+        let map = match &self.map {
+            MapSource::Replicated { path, depth, dz } => {
+                check_file(path)?;
+                OccupancyMap3D::try_replicate_from(path, *depth, *dz)?
+            }
+            MapSource::Layers { paths, dz } => {
+                for path in paths {
+                    check_file(path)?;
+                }
+                let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+                OccupancyMap3D::try_from_files(&paths, *dz)?
+            }
+            _ => return Err(invalid_data("A cena não usa um mapa de imagem 3D")),
+        };
+        Ok(match self.robot {
+            RobotModel::Disk { radius } => map.inflate(RobotRadius::Pixels(radius)),
+            _ => map,
+        })
+    }
+
+    pub fn primitive_map(&self) -> io::Result<PrimitiveMap> {
+        let MapSource::Primitives { lower, upper, obstacles } = &self.map else {
+            return Err(invalid_data("A cena não usa um mundo de primitivos"));
+        };
+        let obstacles = obstacles.iter().map(PrimitiveSpec::to_primitive).collect::<io::Result<Vec<_>>>()?;
+        let map = PrimitiveMap::new(point3(lower), point3(upper)).with_obstacles(obstacles);
+        Ok(match self.robot {
+            RobotModel::Disk { radius } => map.with_clearance(radius),
+            _ => map,
+        })
+    }
+
+    pub fn arm_map(&self) -> io::Result<ArmMap> {
+        let MapSource::Arm { obstacles } = &self.map else {
+            return Err(invalid_data("A cena não usa a bancada do braço"));
+        };
+        let map = ArmMap::new(obstacles.iter().map(ArmObstacleSpec::to_obstacle).collect());
+        let mode = match &self.robot {
+            RobotModel::Arm { collision, link_steps, link_radius } => match collision {
+                ArmCollision::Hand => ArmCollisionMode::Hand,
+                ArmCollision::Interpolated => ArmCollisionMode::Interpolated { steps: *link_steps },
+                ArmCollision::Capsule => ArmCollisionMode::Capsule { radius: *link_radius },
+            },
+            RobotModel::Point => ArmCollisionMode::default(),
+            _ => return Err(invalid_data("A bancada do braço só aceita o robô \"arm\"")),
+        };
+        Ok(map.with_collision_mode(mode))
+    }
+
+    // Contorno do robô, se ele não é pontual nem um disco
+    pub fn footprint(&self) -> io::Result<Option<Footprint>> {
+        match &self.robot {
+            RobotModel::Polygon { vertices } => Footprint::polygon(vertices.iter().map(|v| (v[0], v[1])).collect()).map(Some),
+            RobotModel::Rectangle { length, width } => Footprint::rectangle(*length, *width).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    // Monta o mapa e o planejador descritos na cena, planeja e grava as saídas
+    pub fn solve(&self) -> io::Result<Outcome> {
+        match &self.map {
+            MapSource::Image { .. } => match self.footprint()? {
+                Some(footprint) => self.solve_geometric(FootprintMap::new(self.occupancy_map()?, footprint)),
+                // O uniciclo se move no plano, então só combina com o mapa 2D de pontos
+                None => match (self.planner.algorithm.is_kinodynamic(), self.planner.dynamics) {
+                    (true, DynamicsModel::Unicycle { min_speed, max_speed, max_turn_rate }) => {
+                        self.solve_kinodynamic(self.occupancy_map()?, Unicycle::new(min_speed, max_speed, max_turn_rate))
+                    }
+                    _ => self.solve_geometric(self.occupancy_map()?),
+                },
+            },
+            MapSource::Replicated { .. } | MapSource::Layers { .. } => self.solve_geometric(self.occupancy_map_3d()?),
+            MapSource::Primitives { .. } => self.solve_geometric(self.primitive_map()?),
+            MapSource::Arm { .. } => self.solve_arm(),
+        }
+    }
+
+    // Igual a solve, informando o andamento no terminal. Devolve Ok(true) se encontrou caminho.
+    pub fn run(&self) -> io::Result<bool> {
+        match self.map {
+            MapSource::Arm { .. } => println!("Iniciando planejamento do braço..."),
+            _ => println!("Iniciando planejamento ({:?})...", self.planner.algorithm),
+        }
+        let outcome = self.solve()?;
+        if let Some(file) = &self.output.nodes {
+            println!("Nós da árvore salvos em {}", file);
+        }
+        match (outcome.path_len, &self.output.path) {
+            (Some(len), Some(file)) => println!("Caminho com {} pontos salvo em {}", len, file),
+            (Some(len), None) => println!("Caminho encontrado com {} pontos.", len),
+            (None, _) => println!("Não foi possível encontrar um caminho."),
+        }
+        Ok(outcome.found())
+    }
+
+    fn solve_geometric<M: ConfigurationMap>(&self, map: M) -> io::Result<Outcome> {
+        let p = &self.planner;
+        if let (true, DynamicsModel::DoubleIntegrator { max_speed, max_accel }) = (p.algorithm.is_kinodynamic(), p.dynamics) {
+            return self.solve_kinodynamic(map, DoubleIntegrator::<M::State>::new(max_speed, max_accel));
+        }
+        let (start, goal) = self.endpoints::<M::State>()?;
+        if !map.is_free(&start) {
+            return Err(invalid_input("O início colide com um obstáculo ou está fora do mapa"));
+        }
+        let timer = Instant::now();
+
+        match p.algorithm {
+            Algorithm::RrtConnect => {
+                let mut planner = RRTConnectPlanner::new(start, goal, map, p.step_size, p.max_iter, p.collision_steps);
+                if let Some(seed) = p.seed {
+                    planner = planner.with_seed(seed);
+                }
+                let path_len = planner.plan().map(|path| path.len());
+                let outcome = Outcome { path_len, cost: planner.path_cost(), nodes: planner.node_count(), seconds: timer.elapsed().as_secs_f64() };
+                self.save(|f| planner.save_nodes_to_csv(f), |f| planner.save_path_to_csv(f), outcome.found())?;
+                Ok(outcome)
+            }
+            Algorithm::Prm | Algorithm::LazyPrm => {
+                let mut planner =
+                    PRMPlanner::new(map, p.num_samples, p.collision_steps).with_lazy(p.algorithm == Algorithm::LazyPrm);
+                if let Some(seed) = p.seed {
+                    planner = planner.with_seed(seed);
+                }
+                let path = planner.query(start, goal);
+                let space = planner.map().space();
+                let outcome = Outcome {
+                    path_len: path.as_ref().map(Vec::len),
+                    cost: path.as_ref().map(|path| path_length(&space, path)),
+                    nodes: planner.nodes.len(),
+                    seconds: timer.elapsed().as_secs_f64(),
+                };
+                // O roadmap vai no formato próprio do PRM (ver load_roadmap)
+                self.save(|f| planner.save_roadmap(f), |f| write_path_csv(f, path.as_deref().unwrap_or_default()), outcome.found())?;
+                Ok(outcome)
+            }
+            Algorithm::FmtStar => {
+                let mut planner = FMTPlanner::new(map, p.num_samples, p.collision_steps);
+                if let Some(seed) = p.seed {
+                    planner = planner.with_seed(seed);
+                }
+                let path = planner.plan(start, goal).cloned();
+                let outcome = Outcome {
+                    path_len: path.as_ref().map(Vec::len),
+                    cost: planner.path_cost(),
+                    nodes: planner.nodes.len(),
+                    seconds: timer.elapsed().as_secs_f64(),
+                };
+                self.save(|_| Ok(()), |f| write_path_csv(f, path.as_deref().unwrap_or_default()), outcome.found())?;
+                Ok(outcome)
+            }
+            Algorithm::BitStar => {
+                let mut planner =
+                    BITStarPlanner::new(map, start, goal, p.num_samples, p.collision_steps).with_max_batches(p.max_batches);
+                if let Some(seed) = p.seed {
+                    planner = planner.with_seed(seed);
+                }
+                let path = planner.plan().cloned();
+                let space = planner.map().space();
+                let outcome = Outcome {
+                    path_len: path.as_ref().map(Vec::len),
+                    cost: path.as_ref().map(|path| path_length(&space, path)),
+                    nodes: planner.node_count(),
+                    seconds: timer.elapsed().as_secs_f64(),
+                };
+                self.save(|_| Ok(()), |f| write_path_csv(f, path.as_deref().unwrap_or_default()), outcome.found())?;
+                Ok(outcome)
+            }
+            Algorithm::Est => {
+                let mut planner = ESTPlanner::new(start, goal, map, p.step_size, p.goal_radius, p.collision_steps)
+                    .with_goal_bias(p.goal_bias)
+                    .with_max_iter(p.max_iter);
+                if let Some(seed) = p.seed {
+                    planner = planner.with_seed(seed);
+                }
+                let path_len = planner.plan().map(|path| path.len());
+                let outcome = Outcome { path_len, cost: planner.path_cost(), nodes: planner.tree().len(), seconds: timer.elapsed().as_secs_f64() };
+                self.save(|f| planner.save_nodes_to_csv(f), |f| planner.save_path_to_csv(f), outcome.found())?;
+                Ok(outcome)
+            }
+            Algorithm::Kpiece => {
+                let mut planner = KPIECEPlanner::new(start, goal, map, p.step_size, p.goal_radius, p.collision_steps)
+                    .with_goal_bias(p.goal_bias)
+                    .with_max_iter(p.max_iter);
+                if let Some(seed) = p.seed {
+                    planner = planner.with_seed(seed);
+                }
+                let path_len = planner.plan().map(|path| path.len());
+                let outcome = Outcome { path_len, cost: planner.path_cost(), nodes: planner.tree().len(), seconds: timer.elapsed().as_secs_f64() };
+                self.save(|f| planner.save_nodes_to_csv(f), |f| planner.save_path_to_csv(f), outcome.found())?;
+                Ok(outcome)
+            }
+            Algorithm::Rrt | Algorithm::RrtStar | Algorithm::InformedRrtStar => {
+                let mut planner = TreePlanner::new(start, goal, map, p.step_size, p.goal_radius)
+                    .with_goal_bias(p.goal_bias)
+                    .with_max_iter(p.max_iter)
+                    .with_collision_steps(p.collision_steps);
+                if let Some(seed) = p.seed {
+                    planner = planner.with_seed(seed);
+                }
+                let path_len = match p.algorithm {
+                    Algorithm::RrtStar => planner.plan_star(),
+                    Algorithm::InformedRrtStar => planner.plan_informed_star(),
+                    _ => planner.plan(),
+                }
+                .map(|path| path.len());
+                let outcome = Outcome { path_len, cost: planner.path_cost(), nodes: planner.tree().len(), seconds: timer.elapsed().as_secs_f64() };
+                self.save(|f| planner.save_nodes_to_csv(f), |f| planner.save_path_to_csv(f), outcome.found())?;
+                Ok(outcome)
+            }
+            Algorithm::KinodynamicRrt | Algorithm::Sst => Err(invalid_input("O uniciclo só vale em mapas de imagem 2D")),
+        }
+    }
+
+    // Início e objetivo dos planejadores cinodinâmicos: o estado completo do modelo ou só a
+    // configuração, com o resto (velocidade, orientação) em zero
+    fn dynamic_endpoints<D: Dynamics>(&self) -> io::Result<(D::State, D::State)> {
+        let state = |name: &str, coords: &[f64]| {
+            if coords.len() == D::Config::DIM {
+                let mut full = coords.to_vec();
+                full.resize(D::State::DIM, 0.0);
+                Ok(D::State::from_coords(&full))
+            } else {
+                state_from::<D::State>(name, coords)
+            }
+        };
+        Ok((state("start", &self.start)?, state("goal", &self.goal)?))
+    }
+
+    // O custo informado é a duração da trajetória, em segundos
+    fn solve_kinodynamic<M: ConfigurationMap, D: Dynamics<Config = M::State>>(&self, map: M, dynamics: D) -> io::Result<Outcome> {
+        let (start, goal) = self.dynamic_endpoints::<D>()?;
+        let p = &self.planner;
+        let start_error = || invalid_input("O estado inicial colide com um obstáculo ou está fora dos limites do modelo");
+        let timer = Instant::now();
+
+        if p.algorithm == Algorithm::Sst {
+            let mut planner = SSTPlanner::new(start, goal, map, dynamics, p.dt, p.goal_radius)
+                .with_goal_bias(p.goal_bias)
+                .with_max_iter(p.max_iter)
+                .with_collision_steps(p.collision_steps);
+            if let Some(seed) = p.seed {
+                planner = planner.with_seed(seed);
+            }
+            if !planner.start_is_free() {
+                return Err(start_error());
+            }
+            let path_len = planner.plan().map(|trajectory| trajectory.len());
+            let outcome = Outcome { path_len, cost: planner.duration(), nodes: planner.node_count(), seconds: timer.elapsed().as_secs_f64() };
+            self.save(|f| planner.save_nodes_to_csv(f), |f| planner.save_trajectory_to_csv(f), outcome.found())?;
+            Ok(outcome)
+        } else {
+            let mut planner = KinodynamicPlanner::new(start, goal, map, dynamics, p.dt, p.goal_radius)
+                .with_goal_bias(p.goal_bias)
+                .with_max_iter(p.max_iter)
+                .with_collision_steps(p.collision_steps);
+            if let Some(seed) = p.seed {
+                planner = planner.with_seed(seed);
+            }
+            if !planner.start_is_free() {
+                return Err(start_error());
+            }
+            let path_len = planner.plan().map(|trajectory| trajectory.len());
+            let outcome = Outcome { path_len, cost: planner.duration(), nodes: planner.tree().len(), seconds: timer.elapsed().as_secs_f64() };
+            self.save(|f| planner.save_nodes_to_csv(f), |f| planner.save_trajectory_to_csv(f), outcome.found())?;
+            Ok(outcome)
+        }
+    }
+
+    fn solve_arm(&self) -> io::Result<Outcome> {
+        let map = self.arm_map()?;
+        // Início em juntas; objetivo é o ponto XYZ da garra
+        let start: Configuration = state_from("start", &self.start)?;
+        let goal: Point3 = state_from("goal", &self.goal)?;
+        if map.check_collision(&start) {
+            return Err(invalid_input("A configuração inicial colide com um obstáculo ou com o chão"));
+        }
+        let p = &self.planner;
+
+        let mut planner = ArmPlanner::new(start, goal, map, p.step_size, p.goal_radius)
+            .with_max_iter(p.max_iter)
+            .with_collision_steps(p.collision_steps)
+            .with_closest_bias(p.closest_bias);
+        if let Some(seed) = p.seed {
+            planner = planner.with_seed(seed);
+        }
+        let timer = Instant::now();
+        planner.plan();
+        let seconds = timer.elapsed().as_secs_f64();
+        let space = joint_space();
+        let cost = planner.path().map(|path| path_length(&space, path));
+        let outcome = Outcome { path_len: planner.path().map(Vec::len), cost, nodes: planner.tree().len(), seconds };
+        self.save(|f| planner.save_nodes_to_csv(f), |f| planner.save_path_to_csv(f), outcome.found())?;
+        Ok(outcome)
+    }
+
+    fn save(
+        &self,
+        save_nodes: impl FnOnce(&str) -> io::Result<()>,
+        save_path: impl FnOnce(&str) -> io::Result<()>,
+        found: bool,
+    ) -> io::Result<()> {
+        if let Some(file) = &self.output.nodes {
+            save_nodes(file)?;
+        }
+        if let (Some(file), true) = (&self.output.path, found) {
+            save_path(file)?;
+        }
+        Ok(())
+    }
+}
+
+// Resultado de uma execução da cena
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Outcome {
+    pub path_len: Option<usize>, // Pontos do caminho (None se não encontrou)
+    pub cost: Option<f64>,       // Comprimento do caminho na métrica do espaço (duração nos cinodinâmicos)
+    pub nodes: usize,
+    pub seconds: f64, // Tempo de planejamento, sem contar a leitura do mapa
+}
+
+impl Outcome {
+    pub fn found(&self) -> bool {
+        self.path_len.is_some()
+    }
+}
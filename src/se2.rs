@@ -0,0 +1,331 @@
+use std::f64::consts::PI;
+
+use rand::{Rng, RngCore};
+
+use crate::persist::Fingerprint;
+use crate::readmap::*;
+use crate::space::*;
+use crate::structs::Point;
+
+// Pose no plano: posição em pixels e orientação em radianos, em [-π, π)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pose2 {
+    pub x: f64,
+    pub y: f64,
+    pub theta: f64,
+}
+
+impl State for Pose2 {
+    const DIM: usize = 3;
+
+    fn coord(&self, axis: usize) -> f64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.theta,
+        }
+    }
+
+    fn from_coords(coords: &[f64]) -> Pose2 {
+        Pose2 { x: coords[0], y: coords[1], theta: coords[2] }
+    }
+
+    fn axis_name(axis: usize) -> String {
+        ["x", "y", "theta"][axis].to_string()
+    }
+}
+
+// Ângulo equivalente em [-π, π)
+pub fn wrap_angle(angle: f64) -> f64 {
+    (angle + PI).rem_euclid(2.0 * PI) - PI
+}
+
+// Espaço SE(2): caixa em x/y e círculo em θ. A métrica soma a rotação ponderada,
+// d = sqrt(dx² + dy² + (w Δθ)²), com Δθ pelo menor arco. O peso w está em pixels
+// por radiano: com w igual ao raio do robô, girar 1 rad custa o que a ponta anda.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SE2Space {
+    pub lower: Point,
+    pub upper: Point,
+    pub rotation_weight: f64,
+}
+
+impl SE2Space {
+    pub fn new(lower: Point, upper: Point, rotation_weight: f64) -> Self {
+        assert!(rotation_weight > 0.0, "O peso da rotação deve ser positivo");
+        SE2Space { lower, upper, rotation_weight }
+    }
+}
+
+impl StateSpace for SE2Space {
+    type State = Pose2;
+
+    fn distance(&self, a: &Pose2, b: &Pose2) -> f64 {
+        let dtheta = wrap_angle(b.theta - a.theta) * self.rotation_weight;
+        ((b.x - a.x).powi(2) + (b.y - a.y).powi(2) + dtheta * dtheta).sqrt()
+    }
+
+    // Posição em linha reta e orientação pelo menor arco
+    fn interpolate(&self, a: &Pose2, b: &Pose2, t: f64) -> Pose2 {
+        Pose2 {
+            x: a.x + (b.x - a.x) * t,
+            y: a.y + (b.y - a.y) * t,
+            theta: wrap_angle(a.theta + wrap_angle(b.theta - a.theta) * t),
+        }
+    }
+
+    fn sample_uniform(&self, rng: &mut dyn RngCore) -> Pose2 {
+        let x = if self.upper.x > self.lower.x { rng.gen_range(self.lower.x..self.upper.x) } else { self.lower.x };
+        let y = if self.upper.y > self.lower.y { rng.gen_range(self.lower.y..self.upper.y) } else { self.lower.y };
+        Pose2 { x, y, theta: rng.gen_range(-PI..PI) }
+    }
+
+    fn bounds(&self) -> (Pose2, Pose2) {
+        (
+            Pose2 { x: self.lower.x, y: self.lower.y, theta: -PI },
+            Pose2 { x: self.upper.x, y: self.upper.y, theta: PI },
+        )
+    }
+
+    // O eixo θ entra na métrica multiplicado pelo peso
+    fn volume(&self) -> f64 {
+        (self.upper.x - self.lower.x) * (self.upper.y - self.lower.y) * 2.0 * PI * self.rotation_weight
+    }
+
+    // Só x e y podam: perto de ±π, coordenadas distantes em θ são orientações próximas
+    fn axis_distance(&self, axis: usize, a: f64, b: f64) -> f64 {
+        if axis < 2 { (a - b).abs() } else { 0.0 }
+    }
+
+    fn write_fingerprint(&self, hasher: &mut Fingerprint) {
+        hasher.write(b"se2");
+        for v in [self.lower.x, self.lower.y, self.upper.x, self.upper.y, self.rotation_weight] {
+            hasher.write(&v.to_le_bytes());
+        }
+    }
+}
+
+// Contorno do robô no referencial dele (pixels, x para a frente), como polígono simples
+#[derive(Clone, Debug, PartialEq)]
+pub struct Footprint {
+    vertices: Vec<(f64, f64)>,
+}
+
+impl Footprint {
+    // Pelo menos 3 vértices finitos e área não nula (o peso da rotação vem do contorno)
+    pub fn polygon(vertices: Vec<(f64, f64)>) -> std::io::Result<Self> {
+        let invalid = |msg: &str| Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, msg.to_string()));
+        if vertices.len() < 3 {
+            return invalid("O contorno precisa de pelo menos 3 vértices");
+        }
+        if vertices.iter().any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+            return invalid("O contorno tem vértices não finitos");
+        }
+        // Fórmula do laço (shoelace)
+        let n = vertices.len();
+        let area = (0..n)
+            .map(|i| {
+                let (a, b) = (vertices[i], vertices[(i + 1) % n]);
+                a.0 * b.1 - b.0 * a.1
+            })
+            .sum::<f64>()
+            / 2.0;
+        if area.abs() <= 1e-12 {
+            return invalid("O contorno tem área nula");
+        }
+        Ok(Footprint { vertices })
+    }
+
+    // Retângulo centrado na origem, comprimento ao longo de x e largura ao longo de y
+    pub fn rectangle(length: f64, width: f64) -> std::io::Result<Self> {
+        if !(length.is_finite() && length > 0.0 && width.is_finite() && width > 0.0) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("O retângulo precisa de comprimento e largura positivos (recebido {} x {})", length, width),
+            ));
+        }
+        let (hl, hw) = (length / 2.0, width / 2.0);
+        Footprint::polygon(vec![(hl, hw), (-hl, hw), (-hl, -hw), (hl, -hw)])
+    }
+
+    pub fn vertices(&self) -> &[(f64, f64)] {
+        &self.vertices
+    }
+
+    // Raio do círculo centrado na origem que contém o contorno
+    pub fn bounding_radius(&self) -> f64 {
+        self.vertices.iter().map(|&(x, y)| x.hypot(y)).fold(0.0, f64::max)
+    }
+
+    // Vértices no mundo: girados por θ e transladados para (x, y)
+    pub fn transformed(&self, pose: &Pose2) -> Vec<(f64, f64)> {
+        let (sin, cos) = pose.theta.sin_cos();
+        self.vertices.iter().map(|&(x, y)| (pose.x + cos * x - sin * y, pose.y + sin * x + cos * y)).collect()
+    }
+
+    // Pixels cobertos pelo contorno na pose, sem repetição
+    pub fn rasterize(&self, pose: &Pose2) -> Vec<(i64, i64)> {
+        let mut cells = Vec::new();
+        self.any_cell(pose, true, |x, y| {
+            cells.push((x, y));
+            false
+        });
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+    }
+
+    // Visita os pixels do contorno (passos de no máximo meio pixel em cada aresta)
+    // e, com `fill`, os pixels cujo centro está dentro do polígono. Para no primeiro
+    // pixel em que `hit` devolver true.
+    fn any_cell(&self, pose: &Pose2, fill: bool, mut hit: impl FnMut(i64, i64) -> bool) -> bool {
+        let world = self.transformed(pose);
+        let n = world.len();
+
+        for i in 0..n {
+            let (a, b) = (world[i], world[(i + 1) % n]);
+            let steps = ((b.0 - a.0).hypot(b.1 - a.1) * 2.0).ceil().max(1.0) as usize;
+            for k in 0..steps {
+                let t = k as f64 / steps as f64;
+                if hit((a.0 + (b.0 - a.0) * t).round() as i64, (a.1 + (b.1 - a.1) * t).round() as i64) {
+                    return true;
+                }
+            }
+        }
+        if !fill {
+            return false;
+        }
+
+        // Varredura por linhas nos centros de pixel (coordenadas inteiras), regra par-ímpar
+        let y_min = world.iter().map(|p| p.1).fold(f64::INFINITY, f64::min).ceil() as i64;
+        let y_max = world.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max).floor() as i64;
+        let mut crossings: Vec<f64> = Vec::with_capacity(n);
+        for y in y_min..=y_max {
+            let yf = y as f64;
+            crossings.clear();
+            for i in 0..n {
+                let (a, b) = (world[i], world[(i + 1) % n]);
+                if (a.1 <= yf) != (b.1 <= yf) {
+                    crossings.push(a.0 + (yf - a.1) / (b.1 - a.1) * (b.0 - a.0));
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+            for span in crossings.chunks_exact(2) {
+                for x in span[0].ceil() as i64..=span[1].floor() as i64 {
+                    if hit(x, y) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+// Mapa de ocupação visto por um robô com contorno poligonal: a pose (x, y, θ) é livre
+// se nenhum pixel coberto pelo contorno girado e transladado é preto ou fora da imagem.
+// O espaço define a métrica e a curva entre duas poses (padrão: SE2Space, linha reta
+// com giro no lugar; ver steering.rs para robôs tipo carro).
+pub struct FootprintMap<Sp: StateSpace<State = Pose2> = SE2Space> {
+    pub map: OccupancyMap,
+    pub footprint: Footprint,
+    pub space: Sp,
+}
+
+impl FootprintMap {
+    // Espaço nos limites navegáveis do mapa, com peso da rotação igual ao raio envolvente
+    pub fn new(map: OccupancyMap, footprint: Footprint) -> Self {
+        let (x_min_px, y_min_px, x_max_px, y_max_px) = map.pixel_bounds;
+        let space = SE2Space::new(
+            Point { x: x_min_px as f64, y: y_min_px as f64 },
+            Point { x: x_max_px as f64, y: y_max_px as f64 },
+            footprint.bounding_radius(),
+        );
+        FootprintMap { map, footprint, space }
+    }
+
+    pub fn with_rotation_weight(mut self, weight: f64) -> Self {
+        self.space = SE2Space::new(self.space.lower, self.space.upper, weight);
+        self
+    }
+}
+
+impl<Sp: StateSpace<State = Pose2>> FootprintMap<Sp> {
+    // Troca o espaço (métrica e curva de ligação), mantendo mapa e contorno
+    pub fn with_space<T: StateSpace<State = Pose2>>(self, space: T) -> FootprintMap<T> {
+        FootprintMap { map: self.map, footprint: self.footprint, space }
+    }
+
+    fn is_blocked(&self, x: i64, y: i64) -> bool {
+        let (width, height) = self.map.image.dimensions();
+        if x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+            return true;
+        }
+        is_black(self.map.image.get_pixel(x as u32, y as u32))
+    }
+
+    pub fn collides(&self, pose: &Pose2) -> bool {
+        self.footprint.any_cell(pose, true, |x, y| self.is_blocked(x, y))
+    }
+}
+
+impl<Sp: StateSpace<State = Pose2>> ConfigurationMap for FootprintMap<Sp> {
+    type State = Pose2;
+    type Space = Sp;
+
+    fn space(&self) -> Sp {
+        self.space.clone()
+    }
+
+    fn is_free(&self, state: &Pose2) -> bool {
+        !self.collides(state)
+    }
+
+    // Contorno completo nas extremidades e só a borda nas poses intermediárias: um
+    // obstáculo só entra no robô cruzando a borda. Cada um dos num_steps trechos da
+    // curva é subdividido para que nenhum ponto do contorno ande mais de um pixel
+    // entre duas poses verificadas. O tamanho do trecho usa também a distância do
+    // espaço: nas curvas de carro ela é o comprimento do arco (um pedaço de curva ótima
+    // é ótimo), que a corda subestima quando o trecho faz uma volta.
+    fn is_motion_free(&self, a: &Pose2, b: &Pose2, num_steps: i32) -> bool {
+        if self.collides(a) || self.collides(b) {
+            return false;
+        }
+        let radius = self.footprint.bounding_radius();
+        let steps = num_steps.max(1) as usize;
+        let mut prev = *a;
+        for i in 1..=steps {
+            let (t0, t1) = ((i - 1) as f64 / steps as f64, i as f64 / steps as f64);
+            let next = self.space.interpolate(a, b, t1);
+            let chord = (next.x - prev.x).hypot(next.y - prev.y) + wrap_angle(next.theta - prev.theta).abs() * radius;
+            let sweep = chord.max(self.space.distance(&prev, &next));
+            if !sweep.is_finite() {
+                return false; // sem curva entre as poses
+            }
+            let sub = sweep.ceil().max(1.0) as usize;
+            for k in 1..=sub {
+                if i == steps && k == sub {
+                    break; // b já foi verificado
+                }
+                let pose = self.space.interpolate(a, b, t0 + (t1 - t0) * k as f64 / sub as f64);
+                if self.footprint.any_cell(&pose, false, |x, y| self.is_blocked(x, y)) {
+                    return false;
+                }
+            }
+            prev = next;
+        }
+        true
+    }
+
+    // Mapa, contorno e parâmetros do espaço, já que a métrica muda os custos salvos
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = Fingerprint::new();
+        hasher.write(&self.map.fingerprint().to_le_bytes());
+        for &(x, y) in self.footprint.vertices() {
+            hasher.write(&x.to_le_bytes());
+            hasher.write(&y.to_le_bytes());
+        }
+        self.space.write_fingerprint(&mut hasher);
+        hasher.finish()
+    }
+}
@@ -0,0 +1,418 @@
+use rand::{Rng, RngCore};
+
+use crate::persist::Fingerprint;
+use crate::readmap::OccupancyMap;
+use crate::readmap3d::OccupancyMap3D;
+use crate::sampler::MAX_ATTEMPTS;
+use crate::structs::Point;
+use crate::structs3d::Point3;
+
+// Valor de um estado: coordenadas acessíveis por eixo (para CSV, arquivos e índices espaciais)
+pub trait State: Copy + PartialEq + std::fmt::Debug {
+    const DIM: usize;
+
+    fn coord(&self, axis: usize) -> f64;
+    fn from_coords(coords: &[f64]) -> Self;
+
+    fn coords(&self) -> Vec<f64> {
+        (0..Self::DIM).map(|axis| self.coord(axis)).collect()
+    }
+
+    // Nome do eixo nos CSVs e arquivos salvos
+    fn axis_name(axis: usize) -> String {
+        axis_name(axis, Self::DIM)
+    }
+}
+
+impl State for Point {
+    const DIM: usize = 2;
+
+    fn coord(&self, axis: usize) -> f64 {
+        match axis {
+            0 => self.x,
+            _ => self.y,
+        }
+    }
+
+    fn from_coords(coords: &[f64]) -> Point {
+        Point { x: coords[0], y: coords[1] }
+    }
+}
+
+impl State for Point3 {
+    const DIM: usize = 3;
+
+    fn coord(&self, axis: usize) -> f64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    fn from_coords(coords: &[f64]) -> Point3 {
+        Point3 { x: coords[0], y: coords[1], z: coords[2] }
+    }
+}
+
+// Vetores de tamanho fixo, ex.: [f64; 4] para o espaço de juntas do RoArm
+impl<const N: usize> State for [f64; N] {
+    const DIM: usize = N;
+
+    fn coord(&self, axis: usize) -> f64 {
+        self[axis]
+    }
+
+    fn from_coords(coords: &[f64]) -> Self {
+        let mut s = [0.0; N];
+        s.copy_from_slice(&coords[..N]);
+        s
+    }
+}
+
+// Nome do eixo nos CSVs: x, y, z até 3D, q0, q1, ... acima disso
+pub fn axis_name(axis: usize, dim: usize) -> String {
+    if dim <= 3 {
+        ["x", "y", "z"][axis].to_string()
+    } else {
+        format!("q{}", axis)
+    }
+}
+
+// Espaço de estados: métrica, interpolação, amostragem e limites.
+// Os planejadores genéricos só conversam com o espaço através desta interface.
+pub trait StateSpace: Clone {
+    type State: State;
+
+    fn distance(&self, a: &Self::State, b: &Self::State) -> f64;
+    // Estado a uma fração t do caminho de a até b
+    fn interpolate(&self, a: &Self::State, b: &Self::State, t: f64) -> Self::State;
+    fn sample_uniform(&self, rng: &mut dyn RngCore) -> Self::State;
+    // Cantos inferior e superior da região amostrada
+    fn bounds(&self) -> (Self::State, Self::State);
+
+    // Leva um ponto do cubo unitário [0, 1)^d para o espaço (usado pelas sequências
+    // de baixa discrepância). Padrão: mapeamento linear entre os limites.
+    fn unit_to_state(&self, u: &[f64]) -> Self::State {
+        let (lower, upper) = self.bounds();
+        let coords: Vec<f64> = (0..Self::State::DIM)
+            .map(|axis| lower.coord(axis) + u[axis] * (upper.coord(axis) - lower.coord(axis)))
+            .collect();
+        Self::State::from_coords(&coords)
+    }
+
+    fn dimension(&self) -> usize {
+        Self::State::DIM
+    }
+
+    // Volume (área em 2D) da região amostrada, usado nos raios do PRM*/RRT*/FMT*
+    fn volume(&self) -> f64 {
+        let (lower, upper) = self.bounds();
+        (0..Self::State::DIM).map(|axis| upper.coord(axis) - lower.coord(axis)).product()
+    }
+
+    // Amostra no subconjunto informado {x : d(start, x) + d(x, goal) <= c_best}.
+    // Padrão: rejeição sobre amostras uniformes, voltando à uniforme se demorar demais.
+    fn sample_informed(&self, start: &Self::State, goal: &Self::State, c_best: f64, rng: &mut dyn RngCore) -> Self::State {
+        for _ in 0..1000 {
+            let q = self.sample_uniform(rng);
+            if self.distance(start, &q) + self.distance(&q, goal) <= c_best {
+                return q;
+            }
+        }
+        self.sample_uniform(rng)
+    }
+
+    // Passo de no máximo step_size de `from` em direção a `to`
+    fn steer(&self, from: &Self::State, to: &Self::State, step_size: f64) -> Self::State {
+        let d = self.distance(from, to);
+        if d <= step_size { *to } else { self.interpolate(from, to, step_size / d) }
+    }
+
+    // Cota inferior da distância entre dois estados cujas coordenadas no eixo diferem
+    // pelo menos |a - b|. Usada pelo KD-tree para podar; 0 desliga a poda no eixo.
+    fn axis_distance(&self, _axis: usize, _a: f64, _b: f64) -> f64 {
+        0.0
+    }
+
+    // Parâmetros que mudam a métrica, para a impressão digital dos arquivos salvos.
+    // Padrão: só os limites; espaços com pesos ou raio de giro acrescentam os seus.
+    fn write_fingerprint(&self, hasher: &mut Fingerprint) {
+        let (lower, upper) = self.bounds();
+        for c in lower.coords().into_iter().chain(upper.coords()) {
+            hasher.write(&c.to_le_bytes());
+        }
+    }
+}
+
+// Caixa euclidiana: métrica L2 sobre as coordenadas, amostragem uniforme em [lower, upper)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EuclideanSpace<S: State> {
+    pub lower: S,
+    pub upper: S,
+}
+
+impl<S: State> EuclideanSpace<S> {
+    pub fn new(lower: S, upper: S) -> Self {
+        EuclideanSpace { lower, upper }
+    }
+}
+
+impl<S: State> StateSpace for EuclideanSpace<S> {
+    type State = S;
+
+    fn distance(&self, a: &S, b: &S) -> f64 {
+        (0..S::DIM).map(|axis| (a.coord(axis) - b.coord(axis)).powi(2)).sum::<f64>().sqrt()
+    }
+
+    fn interpolate(&self, a: &S, b: &S, t: f64) -> S {
+        let coords: Vec<f64> = (0..S::DIM).map(|axis| a.coord(axis) + (b.coord(axis) - a.coord(axis)) * t).collect();
+        S::from_coords(&coords)
+    }
+
+    fn sample_uniform(&self, rng: &mut dyn RngCore) -> S {
+        let coords: Vec<f64> = (0..S::DIM)
+            .map(|axis| {
+                let (lo, hi) = (self.lower.coord(axis), self.upper.coord(axis));
+                if hi > lo { rng.gen_range(lo..hi) } else { lo }
+            })
+            .collect();
+        S::from_coords(&coords)
+    }
+
+    fn bounds(&self) -> (S, S) {
+        (self.lower, self.upper)
+    }
+
+    fn axis_distance(&self, _axis: usize, a: f64, b: f64) -> f64 {
+        (a - b).abs()
+    }
+
+    // Amostra uniforme no hiperesferoide prolato com focos em start/goal e eixo maior c_best.
+    // Os eixos podem ter escalas bem diferentes (ex.: o mapa 3D é uma "placa" fina em z,
+    // com z = camada * dz), então o elipsoide pode ser muito maior que a caixa. Nesse caso
+    // amostra na caixa envolvente do elipsoide recortada pelos limites e rejeita pelo
+    // elipsoide, em vez de amostrar o elipsoide e rejeitar pelos limites.
+    fn sample_informed(&self, start: &S, goal: &S, c_best: f64, rng: &mut dyn RngCore) -> S {
+        let n = S::DIM;
+
+        let c_min = self.distance(start, goal);
+        // Sem folga (c_best = c_min) o elipsoide degenera no segmento start-goal
+        if c_best <= c_min + 1e-9 {
+            let t = rng.gen_range(0.0..=1.0);
+            return self.interpolate(start, goal, t);
+        }
+        let center: Vec<f64> = (0..n).map(|i| (start.coord(i) + goal.coord(i)) / 2.0).collect();
+        let r1 = c_best / 2.0;
+        let r2 = (c_best * c_best - c_min * c_min).max(0.0).sqrt() / 2.0;
+        let radii: Vec<f64> = (0..n).map(|j| if j == 0 { r1 } else { r2 }).collect();
+        let rot = rotation_to_world(start, goal);
+
+        // Caixa envolvente do elipsoide, recortada pelos limites
+        let mut lo = vec![0.0; n];
+        let mut hi = vec![0.0; n];
+        for i in 0..n {
+            let half = (0..n).map(|j| (rot[i][j] * radii[j]).powi(2)).sum::<f64>().sqrt();
+            lo[i] = (center[i] - half).max(self.lower.coord(i));
+            hi[i] = (center[i] + half).min(self.upper.coord(i));
+        }
+        let box_volume: f64 = (0..n).map(|i| (hi[i] - lo[i]).max(0.0)).product();
+        let ellipsoid_volume = unit_ball_volume(n) * radii.iter().product::<f64>();
+        if box_volume <= 0.0 {
+            return self.sample_uniform(rng);
+        }
+
+        let inside_ellipsoid = |p: &[f64]| {
+            (0..n)
+                .map(|j| {
+                    // Coordenada no referencial do elipsoide: rot^T * (p - centro)
+                    let local = (0..n).map(|i| rot[i][j] * (p[i] - center[i])).sum::<f64>();
+                    if radii[j] > 0.0 { (local / radii[j]).powi(2) } else { f64::INFINITY }
+                })
+                .sum::<f64>()
+                <= 1.0
+        };
+
+        for _ in 0..MAX_ATTEMPTS {
+            let p: Vec<f64> = if ellipsoid_volume <= box_volume {
+                let b = sample_unit_ball(rng, n);
+                (0..n).map(|i| center[i] + (0..n).map(|j| rot[i][j] * radii[j] * b[j]).sum::<f64>()).collect()
+            } else {
+                (0..n).map(|i| if hi[i] > lo[i] { rng.gen_range(lo[i]..hi[i]) } else { lo[i] }).collect()
+            };
+            let in_box = (0..n).all(|i| p[i] >= lo[i] && p[i] <= hi[i]);
+            if in_box && inside_ellipsoid(&p) {
+                return S::from_coords(&p);
+            }
+        }
+        self.sample_uniform(rng)
+    }
+}
+
+// Amostra da normal padrão (Box-Muller)
+pub(crate) fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+    let u2: f64 = rng.r#gen();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
+
+// Direção uniforme na esfera unitária de dimensão n
+pub(crate) fn random_direction<R: Rng + ?Sized>(rng: &mut R, n: usize) -> Vec<f64> {
+    loop {
+        let g: Vec<f64> = (0..n).map(|_| standard_normal(rng)).collect();
+        let norm = g.iter().map(|v| v * v).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            return g.iter().map(|v| v / norm).collect();
+        }
+    }
+}
+
+// Ponto uniforme na bola unitária de dimensão n (direção gaussiana, raio u^(1/n))
+fn sample_unit_ball<R: Rng + ?Sized>(rng: &mut R, n: usize) -> Vec<f64> {
+    let r = rng.r#gen::<f64>().powf(1.0 / n as f64);
+    random_direction(rng, n).iter().map(|v| v * r).collect()
+}
+
+// Base ortonormal cuja primeira coluna é a direção start -> goal (Gram-Schmidt)
+fn rotation_to_world<S: State>(start: &S, goal: &S) -> Vec<Vec<f64>> {
+    let n = S::DIM;
+    let diff: Vec<f64> = (0..n).map(|i| goal.coord(i) - start.coord(i)).collect();
+    let norm = diff.iter().map(|v| v * v).sum::<f64>().sqrt();
+
+    let mut basis: Vec<Vec<f64>> = Vec::with_capacity(n);
+    if norm > 0.0 {
+        basis.push(diff.iter().map(|v| v / norm).collect());
+    }
+    for k in 0..n {
+        if basis.len() == n {
+            break;
+        }
+        let mut v: Vec<f64> = (0..n).map(|i| if i == k { 1.0 } else { 0.0 }).collect();
+        for b in &basis {
+            let dot: f64 = (0..n).map(|i| v[i] * b[i]).sum();
+            for i in 0..n {
+                v[i] -= dot * b[i];
+            }
+        }
+        let len = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        if len > 1e-9 {
+            basis.push(v.iter().map(|x| x / len).collect());
+        }
+    }
+
+    // rot[i][j] = componente i do j-ésimo vetor da base
+    (0..n).map(|i| (0..n).map(|j| basis[j][i]).collect()).collect()
+}
+
+// Volume da bola unitária em d dimensões (V0 = 1, V1 = 2, Vd = Vd-2 * 2π/d)
+pub fn unit_ball_volume(dim: usize) -> f64 {
+    match dim {
+        0 => 1.0,
+        1 => 2.0,
+        d => unit_ball_volume(d - 2) * 2.0 * std::f64::consts::PI / d as f64,
+    }
+}
+
+// Mapa visto pelos planejadores genéricos: o espaço de estados onde se amostra
+// e a verificação de colisão de estados e segmentos
+pub trait ConfigurationMap {
+    type State: State;
+    type Space: StateSpace<State = Self::State>;
+
+    fn space(&self) -> Self::Space;
+    fn is_free(&self, state: &Self::State) -> bool;
+
+    // Padrão: verifica num_steps + 1 estados interpolados pelo espaço
+    fn is_motion_free(&self, a: &Self::State, b: &Self::State, num_steps: i32) -> bool {
+        let space = self.space();
+        let steps = num_steps.max(1);
+        (0..=steps).all(|i| self.is_free(&space.interpolate(a, b, i as f64 / steps as f64)))
+    }
+
+    // Impressão digital do conteúdo do mapa, para rejeitar roadmaps de outro mapa
+    fn fingerprint(&self) -> u64;
+}
+
+impl ConfigurationMap for OccupancyMap {
+    type State = Point;
+    type Space = EuclideanSpace<Point>;
+
+    fn space(&self) -> EuclideanSpace<Point> {
+        let (x_min_px, y_min_px, x_max_px, y_max_px) = self.pixel_bounds;
+        EuclideanSpace::new(
+            Point { x: x_min_px as f64, y: y_min_px as f64 },
+            Point { x: x_max_px as f64, y: y_max_px as f64 },
+        )
+    }
+
+    fn is_free(&self, state: &Point) -> bool {
+        !self.is_obstructed(state)
+    }
+
+    fn is_motion_free(&self, a: &Point, b: &Point, num_steps: i32) -> bool {
+        !self.is_path_colliding(a, b, num_steps)
+    }
+
+    fn fingerprint(&self) -> u64 {
+        OccupancyMap::fingerprint(self)
+    }
+}
+
+impl ConfigurationMap for OccupancyMap3D {
+    type State = Point3;
+    type Space = EuclideanSpace<Point3>;
+
+    fn space(&self) -> EuclideanSpace<Point3> {
+        EuclideanSpace::new(
+            Point3 { x: 0.0, y: 0.0, z: 0.0 },
+            Point3 { x: self.width as f64, y: self.height as f64, z: self.depth as f64 * self.dz },
+        )
+    }
+
+    fn is_free(&self, state: &Point3) -> bool {
+        !self.is_obstructed_xyz(state.x, state.y, state.z)
+    }
+
+    fn is_motion_free(&self, a: &Point3, b: &Point3, num_steps: i32) -> bool {
+        !self.is_colliding(a, b, num_steps)
+    }
+
+    fn fingerprint(&self) -> u64 {
+        OccupancyMap3D::fingerprint(self)
+    }
+}
+
+// Mapa definido por um espaço e um predicado de validade. Serve para espaços sem
+// imagem, como o espaço de juntas de um braço, onde a validade vem de cinemática.
+#[derive(Clone)]
+pub struct PredicateMap<Sp: StateSpace, F: Fn(&Sp::State) -> bool> {
+    pub space: Sp,
+    pub is_valid: F,
+    pub name: String, // Entra na impressão digital, já que a função não pode ser comparada
+}
+
+impl<Sp: StateSpace, F: Fn(&Sp::State) -> bool> PredicateMap<Sp, F> {
+    pub fn new(space: Sp, name: &str, is_valid: F) -> Self {
+        PredicateMap { space, is_valid, name: name.to_string() }
+    }
+}
+
+impl<Sp: StateSpace, F: Fn(&Sp::State) -> bool> ConfigurationMap for PredicateMap<Sp, F> {
+    type State = Sp::State;
+    type Space = Sp;
+
+    fn space(&self) -> Sp {
+        self.space.clone()
+    }
+
+    fn is_free(&self, state: &Sp::State) -> bool {
+        (self.is_valid)(state)
+    }
+
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = Fingerprint::new();
+        hasher.write(self.name.as_bytes());
+        self.space.write_fingerprint(&mut hasher);
+        hasher.finish()
+    }
+}
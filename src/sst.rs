@@ -0,0 +1,355 @@
+use rand::Rng;
+use rand::rngs::StdRng;
+
+use crate::dynamics::*;
+use crate::kdtree::KdTree;
+use crate::kinodynamic::*;
+use crate::sampler::planner_rng;
+use crate::space::*;
+use crate::tree::*;
+
+#[derive(Clone, Debug)]
+struct SstNode<S, U> {
+    state: S,
+    parent: Option<usize>,
+    edge: Option<(U, usize)>, // Controle e passos vindos do pai
+    steps_from_root: usize,   // Custo: instante do nó em passos de dt
+    children: usize,
+    active: bool,
+    index_pos: Option<usize>, // Posição no índice dos ativos
+}
+
+// SST (Stable Sparse RRT): RRT cinodinâmico assintoticamente quase ótimo que mantém a
+// árvore esparsa. O espaço é coberto por testemunhas a witness_radius umas das outras;
+// cada testemunha guarda só o nó de menor custo (tempo) na sua vizinhança. Nós piores
+// ficam inativos e, quando viram folhas, são removidos junto com os ancestrais inativos,
+// então a memória fica limitada pelo número de testemunhas em vez de crescer sem fim.
+pub struct SSTPlanner<M: ConfigurationMap, D: Dynamics<Config = M::State>> {
+    start: D::State,
+    goal: D::State,
+    model: Propagator<M, D>,
+    seed: Option<u64>,
+    rng: StdRng,
+    selection_radius: f64,
+    witness_radius: f64,
+    control_samples: usize,
+    goal_radius: f64,
+    goal_bias: f64,
+    max_iter: u32,
+
+    // Nós em posições reaproveitadas: posições livres vão para `free`
+    nodes: Vec<Option<SstNode<D::State, D::Control>>>,
+    free: Vec<usize>,
+    // Índice dos nós ativos; posições de nós que deixaram de ser ativos ficam obsoletas
+    // até a próxima reconstrução
+    active_index: KdTree<D::State>,
+    active_slots: Vec<usize>,
+    stale: usize,
+    witnesses: KdTree<D::State>,
+    representatives: Vec<Option<usize>>,
+    best_steps: Option<usize>,
+    trajectory: Option<Vec<TimedState<D::State, D::Control>>>,
+}
+
+impl<M: ConfigurationMap, D: Dynamics<Config = M::State>> SSTPlanner<M, D> {
+    // Raios padrão: seleção 2 * goal_radius e testemunhas goal_radius
+    pub fn new(start: D::State, goal: D::State, map: M, dynamics: D, dt: f64, goal_radius: f64) -> Self {
+        let mut planner = Self {
+            start,
+            goal,
+            model: Propagator::new(map, dynamics, dt),
+            seed: None,
+            rng: planner_rng(None),
+            selection_radius: 2.0 * goal_radius,
+            witness_radius: goal_radius,
+            control_samples: 1,
+            goal_radius,
+            goal_bias: 0.05,
+            max_iter: 20_000,
+            nodes: Vec::new(),
+            free: Vec::new(),
+            active_index: KdTree::new(),
+            active_slots: Vec::new(),
+            stale: 0,
+            witnesses: KdTree::new(),
+            representatives: Vec::new(),
+            best_steps: None,
+            trajectory: None,
+        };
+        planner.clear();
+        planner
+    }
+
+    pub fn with_goal_bias(mut self, bias: f64) -> Self {
+        self.goal_bias = bias.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_max_iter(mut self, iters: u32) -> Self {
+        self.max_iter = iters;
+        self
+    }
+
+    // Pontos verificados entre dois passos de integração consecutivos (padrão: 1, só os passos)
+    pub fn with_collision_steps(mut self, steps: i32) -> Self {
+        self.model.collision_steps = steps.max(1);
+        self
+    }
+
+    // Duração de cada controle sorteado, em passos de dt (padrão: 1 a 10)
+    pub fn with_duration_steps(mut self, min_steps: usize, max_steps: usize) -> Self {
+        self.model.min_steps = min_steps.max(1);
+        self.model.max_steps = max_steps.max(self.model.min_steps);
+        self
+    }
+
+    // Controles sorteados por expansão (padrão: 1, a propagação de Monte Carlo do SST)
+    pub fn with_control_samples(mut self, samples: usize) -> Self {
+        self.control_samples = samples.max(1);
+        self
+    }
+
+    // Raio da seleção "melhor primeiro": o nó ativo de menor custo perto da amostra
+    pub fn with_selection_radius(mut self, radius: f64) -> Self {
+        self.selection_radius = radius;
+        self
+    }
+
+    // Raio das testemunhas: quanto maior, mais esparsa a árvore
+    pub fn with_witness_radius(mut self, radius: f64) -> Self {
+        self.witness_radius = radius;
+        self
+    }
+
+    // Semente do gerador: mesma semente e mesmo mapa reproduzem exatamente o resultado
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self.rng = planner_rng(self.seed);
+        self
+    }
+
+    pub fn map(&self) -> &M {
+        &self.model.map
+    }
+
+    pub fn space(&self) -> &D::Space {
+        &self.model.space
+    }
+
+    pub fn trajectory(&self) -> Option<&Vec<TimedState<D::State, D::Control>>> {
+        self.trajectory.as_ref()
+    }
+
+    // Duração da melhor trajetória encontrada
+    pub fn duration(&self) -> Option<f64> {
+        self.trajectory.as_ref().and_then(|t| t.last()).map(|p| p.time)
+    }
+
+    // Nós guardados (ativos e inativos)
+    pub fn node_count(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    pub fn witness_count(&self) -> usize {
+        self.witnesses.len()
+    }
+
+    // Árvore só com a raiz, que é a representante da primeira testemunha
+    fn clear(&mut self) {
+        self.nodes = vec![Some(SstNode {
+            state: self.start,
+            parent: None,
+            edge: None,
+            steps_from_root: 0,
+            children: 0,
+            active: true,
+            index_pos: Some(0),
+        })];
+        self.free.clear();
+        self.active_index = KdTree::from_points(vec![self.start]);
+        self.active_slots = vec![0];
+        self.stale = 0;
+        self.witnesses = KdTree::from_points(vec![self.start]);
+        self.representatives = vec![Some(0)];
+        self.best_steps = None;
+        self.trajectory = None;
+    }
+
+    fn node(&self, slot: usize) -> &SstNode<D::State, D::Control> {
+        self.nodes[slot].as_ref().expect("Nó removido")
+    }
+
+    fn node_mut(&mut self, slot: usize) -> &mut SstNode<D::State, D::Control> {
+        self.nodes[slot].as_mut().expect("Nó removido")
+    }
+
+    // Posição do índice que ainda aponta para um nó ativo
+    fn is_live(&self, pos: usize) -> bool {
+        let slot = self.active_slots[pos];
+        self.nodes[slot].as_ref().is_some_and(|n| n.active && n.index_pos == Some(pos))
+    }
+
+    fn add_node(&mut self, state: D::State, parent: usize, edge: (D::Control, usize)) -> usize {
+        let node = SstNode {
+            state,
+            parent: Some(parent),
+            edge: Some(edge),
+            steps_from_root: self.node(parent).steps_from_root + edge.1,
+            children: 0,
+            active: true,
+            index_pos: Some(self.active_index.len()),
+        };
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.nodes[slot] = Some(node);
+                slot
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.node_mut(parent).children += 1;
+        self.active_index.insert(state);
+        self.active_slots.push(slot);
+        slot
+    }
+
+    // Tira o nó do conjunto ativo e remove a cadeia de folhas inativas a partir dele
+    fn prune(&mut self, slot: usize) {
+        let node = self.node_mut(slot);
+        node.active = false;
+        node.index_pos = None;
+        self.stale += 1;
+
+        let mut current = slot;
+        loop {
+            let node = self.node(current);
+            if node.active || node.children > 0 {
+                break;
+            }
+            let parent = node.parent;
+            self.nodes[current] = None;
+            self.free.push(current);
+            let Some(p) = parent else { break };
+            self.node_mut(p).children -= 1;
+            current = p;
+        }
+
+        // Reconstrói o índice quando as posições obsoletas passam das vivas
+        if self.stale > self.active_slots.len() / 2 {
+            let live: Vec<usize> = (0..self.active_slots.len()).filter(|&pos| self.is_live(pos)).map(|pos| self.active_slots[pos]).collect();
+            self.active_index = KdTree::from_points(live.iter().map(|&slot| self.node(slot).state).collect());
+            for (pos, &slot) in live.iter().enumerate() {
+                self.node_mut(slot).index_pos = Some(pos);
+            }
+            self.active_slots = live;
+            self.stale = 0;
+        }
+    }
+
+    // Seleção "melhor primeiro": o ativo de menor custo a até selection_radius da amostra,
+    // ou o ativo mais próximo se não houver nenhum
+    fn select(&self, target: &D::State) -> usize {
+        let space = &self.model.space;
+        let best = self
+            .active_index
+            .within_radius(space, target, self.selection_radius)
+            .into_iter()
+            .filter(|&(pos, _)| self.is_live(pos))
+            .map(|(pos, _)| self.active_slots[pos])
+            .min_by_key(|&slot| self.node(slot).steps_from_root);
+        best.unwrap_or_else(|| {
+            let (pos, _) = self.active_index.nearest_where(space, target, |pos| self.is_live(pos)).expect("Nenhum nó ativo");
+            self.active_slots[pos]
+        })
+    }
+
+    // Estado inicial dentro dos limites do modelo e livre no mapa; sem isso plan devolve None
+    pub fn start_is_free(&self) -> bool {
+        self.model.is_state_free(&self.start)
+    }
+
+    // Executa max_iter iterações e devolve a trajetória de menor duração encontrada
+    pub fn plan(&mut self) -> Option<&Vec<TimedState<D::State, D::Control>>> {
+        if !self.start_is_free() {
+            return None;
+        }
+
+        for _ in 0..self.max_iter {
+            let target = if self.rng.r#gen::<f64>() < self.goal_bias { self.goal } else { self.model.space.sample_uniform(&mut self.rng) };
+            let selected = self.select(&target);
+            let from = self.node(selected).state;
+            let Some((state, control, steps)) = self.model.random_extension(&from, &target, self.control_samples, &mut self.rng) else {
+                continue;
+            };
+            let cost = self.node(selected).steps_from_root + steps;
+
+            // Só entra se for o melhor da vizinhança da testemunha mais próxima
+            let witness = match self.witnesses.nearest(&self.model.space, &state) {
+                Some((w, d)) if d <= self.witness_radius => w,
+                _ => {
+                    self.representatives.push(None);
+                    self.witnesses.insert(state)
+                }
+            };
+            let peer = self.representatives[witness];
+            if peer.is_some_and(|p| self.node(p).steps_from_root <= cost) {
+                continue;
+            }
+
+            let new_slot = self.add_node(state, selected, (control, steps));
+            self.representatives[witness] = Some(new_slot);
+            if let Some(p) = peer {
+                self.prune(p);
+            }
+
+            let reaches_goal = self.model.configuration_distance(&state, &self.goal) <= self.goal_radius;
+            if reaches_goal && self.best_steps.is_none_or(|best| cost < best) {
+                self.best_steps = Some(cost);
+                self.trajectory = Some(self.build_trajectory(new_slot));
+            }
+        }
+        self.trajectory.as_ref()
+    }
+
+    // A trajetória é guardada inteira porque os nós dela podem ser podados depois
+    fn build_trajectory(&self, end_slot: usize) -> Vec<TimedState<D::State, D::Control>> {
+        let mut chain = Vec::new();
+        let mut current = Some(end_slot);
+        while let Some(slot) = current {
+            let node = self.node(slot);
+            chain.push((node.state, node.steps_from_root, node.edge));
+            current = node.parent;
+        }
+        chain.reverse();
+        self.model.trajectory(&chain)
+    }
+
+    // Descarta a árvore, as testemunhas e a trajetória, recomeçando do estado inicial
+    // (e da mesma sequência aleatória, se houver semente)
+    pub fn reset(&mut self) {
+        self.rng = planner_rng(self.seed);
+        self.clear();
+    }
+
+    // Nós guardados no esquema único de CSV (posições compactadas, raiz primeiro)
+    pub fn save_nodes_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        let slots: Vec<usize> = (0..self.nodes.len()).filter(|&s| self.nodes[s].is_some()).collect();
+        let mut compact = vec![usize::MAX; self.nodes.len()];
+        for (i, &slot) in slots.iter().enumerate() {
+            compact[slot] = i;
+        }
+        let coords = slots.iter().map(|&s| self.node(s).state).collect();
+        let parents = slots.iter().map(|&s| self.node(s).parent.map(|p| compact[p])).collect();
+        let tree = Tree::from_parents(&self.model.space, coords, parents);
+        write_nodes_csv(filename, &[&tree])
+    }
+
+    pub fn save_trajectory_to_csv(&self, filename: &str) -> std::io::Result<()> {
+        match &self.trajectory {
+            Some(trajectory) => write_trajectory_csv(filename, trajectory),
+            None => Err(std::io::Error::new(std::io::ErrorKind::NotFound, "Nenhuma trajetória final para salvar.")),
+        }
+    }
+}
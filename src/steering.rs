@@ -0,0 +1,482 @@
+use std::f64::consts::PI;
+
+use rand::{Rng, RngCore};
+
+use crate::persist::Fingerprint;
+use crate::se2::*;
+use crate::space::*;
+use crate::structs::Point;
+
+// Tolerância das fórmulas fechadas (comprimentos normalizados pelo raio de giro)
+const ZERO: f64 = 1e-9;
+
+// Trecho elementar de uma curva de carro: arco à esquerda, reta ou arco à direita
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Segment {
+    Left,
+    Straight,
+    Right,
+}
+
+use Segment::*;
+
+// Curva de carro: sequência de trechos com comprimentos normalizados pelo raio de giro
+// (ângulo em radianos nos arcos). Comprimento negativo é o trecho feito de ré.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CarPath {
+    pub segments: Vec<(Segment, f64)>,
+}
+
+impl CarPath {
+    fn new(types: &[Segment], lengths: &[f64]) -> Self {
+        CarPath { segments: types.iter().copied().zip(lengths.iter().copied()).collect() }
+    }
+
+    // Comprimento normalizado (multiplicar pelo raio de giro para obter pixels)
+    pub fn length(&self) -> f64 {
+        self.segments.iter().map(|(_, l)| l.abs()).sum()
+    }
+
+    // Pose a uma distância normalizada s do início, partindo de `from`
+    pub fn pose_at(&self, from: &Pose2, turning_radius: f64, s: f64) -> Pose2 {
+        let (mut x, mut y, mut theta) = (0.0, 0.0, from.theta);
+        let mut remaining = s.max(0.0);
+        for &(segment, length) in &self.segments {
+            if remaining <= 0.0 {
+                break;
+            }
+            let l = length.signum() * length.abs().min(remaining);
+            remaining -= l.abs();
+            match segment {
+                Left => {
+                    x += (theta + l).sin() - theta.sin();
+                    y += -(theta + l).cos() + theta.cos();
+                    theta += l;
+                }
+                Right => {
+                    x += -(theta - l).sin() + theta.sin();
+                    y += (theta - l).cos() - theta.cos();
+                    theta -= l;
+                }
+                Straight => {
+                    x += l * theta.cos();
+                    y += l * theta.sin();
+                }
+            }
+        }
+        Pose2 { x: from.x + x * turning_radius, y: from.y + y * turning_radius, theta: wrap_angle(theta) }
+    }
+
+    // Poses ao longo da curva a no máximo `resolution` pixels uma da outra, com as extremidades
+    pub fn sample(&self, from: &Pose2, turning_radius: f64, resolution: f64) -> Vec<Pose2> {
+        let total = self.length();
+        let steps = (total * turning_radius / resolution).ceil().max(1.0) as usize;
+        (0..=steps).map(|i| self.pose_at(from, turning_radius, total * i as f64 / steps as f64)).collect()
+    }
+}
+
+// Ângulo em [0, 2π)
+fn mod2pi(angle: f64) -> f64 {
+    angle.rem_euclid(2.0 * PI)
+}
+
+// Pose final relativa à inicial, no referencial da inicial e em unidades do raio de giro
+fn relative(a: &Pose2, b: &Pose2, turning_radius: f64) -> (f64, f64, f64) {
+    let (dx, dy) = ((b.x - a.x) / turning_radius, (b.y - a.y) / turning_radius);
+    let (s, c) = a.theta.sin_cos();
+    (c * dx + s * dy, -s * dx + c * dy, b.theta - a.theta)
+}
+
+// Curvas de Dubins (só para a frente): a mais curta das seis palavras CSC e CCC
+pub fn dubins_path(a: &Pose2, b: &Pose2, turning_radius: f64) -> CarPath {
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let d = dx.hypot(dy) / turning_radius;
+    let th = dy.atan2(dx);
+    let alpha = mod2pi(a.theta - th);
+    let beta = mod2pi(b.theta - th);
+    if d < ZERO && (alpha - beta).abs() < ZERO {
+        return CarPath::new(&[Left, Straight, Left], &[0.0, 0.0, 0.0]);
+    }
+
+    let (sa, ca) = alpha.sin_cos();
+    let (sb, cb) = beta.sin_cos();
+    let cab = ca * cb + sa * sb;
+    let mut candidates: Vec<CarPath> = Vec::with_capacity(6);
+
+    let tmp = 2.0 + d * d - 2.0 * (cab - d * (sa - sb));
+    if tmp >= -ZERO {
+        let theta = (cb - ca).atan2(d + sa - sb);
+        candidates.push(CarPath::new(&[Left, Straight, Left], &[mod2pi(theta - alpha), tmp.max(0.0).sqrt(), mod2pi(beta - theta)]));
+    }
+    let tmp = 2.0 + d * d - 2.0 * (cab - d * (sb - sa));
+    if tmp >= -ZERO {
+        let theta = (ca - cb).atan2(d - sa + sb);
+        candidates.push(CarPath::new(&[Right, Straight, Right], &[mod2pi(alpha - theta), tmp.max(0.0).sqrt(), mod2pi(theta - beta)]));
+    }
+    let tmp = d * d - 2.0 + 2.0 * (cab - d * (sa + sb));
+    if tmp >= -ZERO {
+        let p = tmp.max(0.0).sqrt();
+        let theta = (ca + cb).atan2(d - sa - sb) - 2.0f64.atan2(p);
+        candidates.push(CarPath::new(&[Right, Straight, Left], &[mod2pi(alpha - theta), p, mod2pi(beta - theta)]));
+    }
+    let tmp = -2.0 + d * d + 2.0 * (cab + d * (sa + sb));
+    if tmp >= -ZERO {
+        let p = tmp.max(0.0).sqrt();
+        let theta = (-ca - cb).atan2(d + sa + sb) - (-2.0f64).atan2(p);
+        candidates.push(CarPath::new(&[Left, Straight, Right], &[mod2pi(theta - alpha), p, mod2pi(theta - beta)]));
+    }
+    let tmp = 0.125 * (6.0 - d * d + 2.0 * (cab + d * (sa - sb)));
+    if tmp.abs() <= 1.0 {
+        let p = 2.0 * PI - tmp.acos();
+        let theta = (ca - cb).atan2(d - sa + sb);
+        let t = mod2pi(alpha - theta + 0.5 * p);
+        candidates.push(CarPath::new(&[Right, Left, Right], &[t, p, mod2pi(alpha - beta - t + p)]));
+    }
+    let tmp = 0.125 * (6.0 - d * d + 2.0 * (cab - d * (sa - sb)));
+    if tmp.abs() <= 1.0 {
+        let p = 2.0 * PI - tmp.acos();
+        let theta = (cb - ca).atan2(d + sa - sb);
+        let t = mod2pi(-alpha + theta + 0.5 * p);
+        candidates.push(CarPath::new(&[Left, Right, Left], &[t, p, mod2pi(beta - alpha - t + p)]));
+    }
+
+    // LSL sempre existe: o seu tmp é uma norma ao quadrado
+    candidates.into_iter().min_by(|x, y| x.length().total_cmp(&y.length())).unwrap()
+}
+
+// Curvas de Reeds-Shepp (para a frente e de ré): as famílias CSC, CCC, CCCC, CCSC e CCSCC
+// do artigo original (fórmulas 8.1 a 8.11), com as variantes por inversão do tempo
+// (x -> -x), reflexão (y -> -y) e percurso de trás para a frente. Entre poses finitas
+// sempre há alguma; None só aparece com entradas degeneradas (NaN, raio nulo).
+pub fn reeds_shepp_path(a: &Pose2, b: &Pose2, turning_radius: f64) -> Option<CarPath> {
+    let (x, y, phi) = relative(a, b, turning_radius);
+    let mut best = RsSearch { path: None, length: f64::INFINITY };
+    rs_csc(x, y, phi, &mut best);
+    rs_ccc(x, y, phi, &mut best);
+    rs_cccc(x, y, phi, &mut best);
+    rs_ccsc(x, y, phi, &mut best);
+    rs_ccscc(x, y, phi, &mut best);
+    best.path
+}
+
+struct RsSearch {
+    path: Option<CarPath>,
+    length: f64,
+}
+
+impl RsSearch {
+    fn offer(&mut self, types: &[Segment], lengths: &[f64]) {
+        let length: f64 = lengths.iter().map(|l| l.abs()).sum();
+        if length < self.length {
+            self.length = length;
+            self.path = Some(CarPath::new(types, lengths));
+        }
+    }
+}
+
+// Reflexão troca esquerda e direita
+fn reflect(types: &[Segment]) -> Vec<Segment> {
+    types
+        .iter()
+        .map(|s| match s {
+            Left => Right,
+            Right => Left,
+            Straight => Straight,
+        })
+        .collect()
+}
+
+fn polar(x: f64, y: f64) -> (f64, f64) {
+    (x.hypot(y), y.atan2(x))
+}
+
+fn tau_omega(u: f64, v: f64, xi: f64, eta: f64, phi: f64) -> (f64, f64) {
+    let delta = wrap_angle(u - v);
+    let a = u.sin() - delta.sin();
+    let b = u.cos() - delta.cos() - 1.0;
+    let t1 = (eta * a - xi * b).atan2(xi * a + eta * b);
+    let t2 = 2.0 * (delta.cos() - v.cos() - u.cos()) + 3.0;
+    let tau = if t2 < 0.0 { wrap_angle(t1 + PI) } else { wrap_angle(t1) };
+    (tau, wrap_angle(tau - u + v - phi))
+}
+
+// 8.1
+fn lp_sp_lp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (u, t) = polar(x - phi.sin(), y - 1.0 + phi.cos());
+    let v = wrap_angle(phi - t);
+    (t >= -ZERO && v >= -ZERO).then_some((t, u, v))
+}
+
+// 8.2
+fn lp_sp_rp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (u1, t1) = polar(x + phi.sin(), y - 1.0 - phi.cos());
+    let u1 = u1 * u1;
+    if u1 < 4.0 {
+        return None;
+    }
+    let u = (u1 - 4.0).sqrt();
+    let t = wrap_angle(t1 + 2.0f64.atan2(u));
+    let v = wrap_angle(t - phi);
+    (t >= -ZERO && v >= -ZERO).then_some((t, u, v))
+}
+
+// 8.3 / 8.4
+fn lp_rm_l(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (u1, theta) = polar(x - phi.sin(), y - 1.0 + phi.cos());
+    if u1 > 4.0 {
+        return None;
+    }
+    let u = -2.0 * (0.25 * u1).asin();
+    let t = wrap_angle(theta + 0.5 * u + PI);
+    let v = wrap_angle(phi - t + u);
+    (t >= -ZERO && u <= ZERO).then_some((t, u, v))
+}
+
+// 8.7
+fn lp_rup_lum_rm(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (xi, eta) = (x + phi.sin(), y - 1.0 - phi.cos());
+    let rho = 0.25 * (2.0 + xi.hypot(eta));
+    if rho > 1.0 {
+        return None;
+    }
+    let u = rho.acos();
+    let (t, v) = tau_omega(u, -u, xi, eta, phi);
+    (t >= -ZERO && v <= ZERO).then_some((t, u, v))
+}
+
+// 8.8
+fn lp_rum_lum_rp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (xi, eta) = (x + phi.sin(), y - 1.0 - phi.cos());
+    let rho = (20.0 - xi * xi - eta * eta) / 16.0;
+    if !(0.0..=1.0).contains(&rho) {
+        return None;
+    }
+    let u = -rho.acos();
+    if u < -0.5 * PI {
+        return None;
+    }
+    let (t, v) = tau_omega(u, u, xi, eta, phi);
+    (t >= -ZERO && v >= -ZERO).then_some((t, u, v))
+}
+
+// 8.9
+fn lp_rm_sm_lm(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (rho, theta) = polar(x - phi.sin(), y - 1.0 + phi.cos());
+    if rho < 2.0 {
+        return None;
+    }
+    let r = (rho * rho - 4.0).sqrt();
+    let u = 2.0 - r;
+    let t = wrap_angle(theta + r.atan2(-2.0));
+    let v = wrap_angle(phi - 0.5 * PI - t);
+    (t >= -ZERO && u <= ZERO && v <= ZERO).then_some((t, u, v))
+}
+
+// 8.10
+fn lp_rm_sm_rm(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (xi, eta) = (x + phi.sin(), y - 1.0 - phi.cos());
+    let (rho, theta) = polar(-eta, xi);
+    if rho < 2.0 {
+        return None;
+    }
+    let t = theta;
+    let u = 2.0 - rho;
+    let v = wrap_angle(t + 0.5 * PI - phi);
+    (t >= -ZERO && u <= ZERO && v <= ZERO).then_some((t, u, v))
+}
+
+// 8.11
+fn lp_rm_s_lm_rp(x: f64, y: f64, phi: f64) -> Option<(f64, f64, f64)> {
+    let (xi, eta) = (x + phi.sin(), y - 1.0 - phi.cos());
+    let (rho, _) = polar(xi, eta);
+    if rho < 2.0 {
+        return None;
+    }
+    let u = 4.0 - (rho * rho - 4.0).sqrt();
+    if u > ZERO {
+        return None;
+    }
+    let t = wrap_angle(((4.0 - u) * xi - 2.0 * eta).atan2(-2.0 * xi + (u - 4.0) * eta));
+    let v = wrap_angle(t - phi);
+    (t >= -ZERO && v >= -ZERO).then_some((t, u, v))
+}
+
+type RsFormula = fn(f64, f64, f64) -> Option<(f64, f64, f64)>;
+
+// Aplica uma fórmula nas quatro variantes: original, inversão do tempo (comprimentos
+// negados), reflexão (esquerda <-> direita) e as duas juntas. `lengths` monta os
+// comprimentos dos trechos a partir de (t, u, v).
+fn rs_variants(x: f64, y: f64, phi: f64, formula: RsFormula, types: &[Segment], lengths: impl Fn(f64, f64, f64) -> Vec<f64>, best: &mut RsSearch) {
+    let reflected = reflect(types);
+    let negate = |l: Vec<f64>| l.into_iter().map(|v| -v).collect::<Vec<f64>>();
+    if let Some((t, u, v)) = formula(x, y, phi) {
+        best.offer(types, &lengths(t, u, v));
+    }
+    if let Some((t, u, v)) = formula(-x, y, -phi) {
+        best.offer(types, &negate(lengths(t, u, v)));
+    }
+    if let Some((t, u, v)) = formula(x, -y, -phi) {
+        best.offer(&reflected, &lengths(t, u, v));
+    }
+    if let Some((t, u, v)) = formula(-x, -y, phi) {
+        best.offer(&reflected, &negate(lengths(t, u, v)));
+    }
+}
+
+// Ponto final visto do fim da curva, para as palavras percorridas de trás para a frente
+fn backwards(x: f64, y: f64, phi: f64) -> (f64, f64) {
+    let (s, c) = phi.sin_cos();
+    (x * c + y * s, x * s - y * c)
+}
+
+fn rs_csc(x: f64, y: f64, phi: f64, best: &mut RsSearch) {
+    rs_variants(x, y, phi, lp_sp_lp, &[Left, Straight, Left], |t, u, v| vec![t, u, v], best);
+    rs_variants(x, y, phi, lp_sp_rp, &[Left, Straight, Right], |t, u, v| vec![t, u, v], best);
+}
+
+fn rs_ccc(x: f64, y: f64, phi: f64, best: &mut RsSearch) {
+    rs_variants(x, y, phi, lp_rm_l, &[Left, Right, Left], |t, u, v| vec![t, u, v], best);
+    let (xb, yb) = backwards(x, y, phi);
+    rs_variants(xb, yb, phi, lp_rm_l, &[Left, Right, Left], |t, u, v| vec![v, u, t], best);
+}
+
+fn rs_cccc(x: f64, y: f64, phi: f64, best: &mut RsSearch) {
+    rs_variants(x, y, phi, lp_rup_lum_rm, &[Left, Right, Left, Right], |t, u, v| vec![t, u, -u, v], best);
+    rs_variants(x, y, phi, lp_rum_lum_rp, &[Left, Right, Left, Right], |t, u, v| vec![t, u, u, v], best);
+}
+
+fn rs_ccsc(x: f64, y: f64, phi: f64, best: &mut RsSearch) {
+    let h = -0.5 * PI;
+    rs_variants(x, y, phi, lp_rm_sm_lm, &[Left, Right, Straight, Left], |t, u, v| vec![t, h, u, v], best);
+    rs_variants(x, y, phi, lp_rm_sm_rm, &[Left, Right, Straight, Right], |t, u, v| vec![t, h, u, v], best);
+    let (xb, yb) = backwards(x, y, phi);
+    rs_variants(xb, yb, phi, lp_rm_sm_lm, &[Left, Straight, Right, Left], |t, u, v| vec![v, u, h, t], best);
+    rs_variants(xb, yb, phi, lp_rm_sm_rm, &[Right, Straight, Right, Left], |t, u, v| vec![v, u, h, t], best);
+}
+
+fn rs_ccscc(x: f64, y: f64, phi: f64, best: &mut RsSearch) {
+    let h = -0.5 * PI;
+    rs_variants(x, y, phi, lp_rm_s_lm_rp, &[Left, Right, Straight, Left, Right], |t, u, v| vec![t, h, u, h, v], best);
+}
+
+// Caixa em x/y e círculo em θ, com os limites usados pelos espaços de carro
+fn sample_pose(lower: &Point, upper: &Point, rng: &mut dyn RngCore) -> Pose2 {
+    let x = if upper.x > lower.x { rng.gen_range(lower.x..upper.x) } else { lower.x };
+    let y = if upper.y > lower.y { rng.gen_range(lower.y..upper.y) } else { lower.y };
+    Pose2 { x, y, theta: rng.gen_range(-PI..PI) }
+}
+
+// Espaço de um carro que só anda para a frente com raio de giro mínimo: a distância
+// é o comprimento da curva de Dubins (não simétrica) e a interpolação anda sobre ela
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DubinsSpace {
+    pub lower: Point,
+    pub upper: Point,
+    pub turning_radius: f64,
+}
+
+impl DubinsSpace {
+    pub fn new(lower: Point, upper: Point, turning_radius: f64) -> Self {
+        assert!(turning_radius > 0.0, "O raio de giro deve ser positivo");
+        DubinsSpace { lower, upper, turning_radius }
+    }
+
+    pub fn path(&self, a: &Pose2, b: &Pose2) -> CarPath {
+        dubins_path(a, b, self.turning_radius)
+    }
+
+    fn curve(&self, a: &Pose2, b: &Pose2) -> Option<CarPath> {
+        Some(self.path(a, b))
+    }
+}
+
+// Mesmo que DubinsSpace, mas com ré: curvas de Reeds-Shepp, distância simétrica
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReedsSheppSpace {
+    pub lower: Point,
+    pub upper: Point,
+    pub turning_radius: f64,
+}
+
+impl ReedsSheppSpace {
+    pub fn new(lower: Point, upper: Point, turning_radius: f64) -> Self {
+        assert!(turning_radius > 0.0, "O raio de giro deve ser positivo");
+        ReedsSheppSpace { lower, upper, turning_radius }
+    }
+
+    pub fn path(&self, a: &Pose2, b: &Pose2) -> Option<CarPath> {
+        reeds_shepp_path(a, b, self.turning_radius)
+    }
+
+    fn curve(&self, a: &Pose2, b: &Pose2) -> Option<CarPath> {
+        self.path(a, b)
+    }
+}
+
+macro_rules! car_space {
+    ($space:ty, $tag:literal) => {
+        impl StateSpace for $space {
+            type State = Pose2;
+
+            // Sem curva entre as poses o custo é infinito e a interpolação fica parada em a
+            fn distance(&self, a: &Pose2, b: &Pose2) -> f64 {
+                self.curve(a, b).map_or(f64::INFINITY, |path| path.length() * self.turning_radius)
+            }
+
+            fn interpolate(&self, a: &Pose2, b: &Pose2, t: f64) -> Pose2 {
+                match self.curve(a, b) {
+                    Some(path) => path.pose_at(a, self.turning_radius, path.length() * t.clamp(0.0, 1.0)),
+                    None => *a,
+                }
+            }
+
+            fn sample_uniform(&self, rng: &mut dyn RngCore) -> Pose2 {
+                sample_pose(&self.lower, &self.upper, rng)
+            }
+
+            fn bounds(&self) -> (Pose2, Pose2) {
+                (
+                    Pose2 { x: self.lower.x, y: self.lower.y, theta: -PI },
+                    Pose2 { x: self.upper.x, y: self.upper.y, theta: PI },
+                )
+            }
+
+            // Girar 1 rad custa pelo menos um raio de giro de caminho
+            fn volume(&self) -> f64 {
+                (self.upper.x - self.lower.x) * (self.upper.y - self.lower.y) * 2.0 * PI * self.turning_radius
+            }
+
+            // A curva é pelo menos tão longa quanto a reta entre as posições
+            fn axis_distance(&self, axis: usize, a: f64, b: f64) -> f64 {
+                if axis < 2 { (a - b).abs() } else { 0.0 }
+            }
+
+            // O tipo entra no hash: Dubins e Reeds-Shepp com o mesmo raio dão custos diferentes
+            fn write_fingerprint(&self, hasher: &mut Fingerprint) {
+                hasher.write($tag);
+                for v in [self.lower.x, self.lower.y, self.upper.x, self.upper.y, self.turning_radius] {
+                    hasher.write(&v.to_le_bytes());
+                }
+            }
+        }
+    };
+}
+
+car_space!(DubinsSpace, b"dubins");
+car_space!(ReedsSheppSpace, b"reeds_shepp");
+
+impl<Sp: StateSpace<State = Pose2>> FootprintMap<Sp> {
+    // Robô tipo carro só para a frente, nos mesmos limites do mapa
+    pub fn with_dubins(self, turning_radius: f64) -> FootprintMap<DubinsSpace> {
+        let (lower, upper) = self.space.bounds();
+        let space = DubinsSpace::new(Point { x: lower.x, y: lower.y }, Point { x: upper.x, y: upper.y }, turning_radius);
+        self.with_space(space)
+    }
+
+    // Robô tipo carro com ré, nos mesmos limites do mapa
+    pub fn with_reeds_shepp(self, turning_radius: f64) -> FootprintMap<ReedsSheppSpace> {
+        let (lower, upper) = self.space.bounds();
+        let space = ReedsSheppSpace::new(Point { x: lower.x, y: lower.y }, Point { x: upper.x, y: upper.y }, turning_radius);
+        self.with_space(space)
+    }
+}
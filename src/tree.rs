@@ -0,0 +1,168 @@
+use crate::kdtree::KdTree;
+use crate::space::*;
+
+#[derive(Debug, Clone)]
+pub struct TreeNode<S> {
+    pub coord: S,
+    pub parent_idx: Option<usize>, // Índice do nó pai no vetor da árvore
+    pub cost: f64,                 // Custo acumulado desde a raiz
+    pub children: Vec<usize>,      // Índices dos filhos (usado no religamento do RRT*)
+}
+
+// Árvore genérica usada por todos os planejadores baseados em RRT, em qualquer espaço
+#[derive(Debug, Clone)]
+pub struct Tree<S> {
+    pub nodes: Vec<TreeNode<S>>,
+    index: KdTree<S>, // Índice espacial das coordenadas, na mesma ordem dos nós
+}
+
+impl<S: State> Tree<S> {
+    pub fn new(root: S) -> Self {
+        Tree {
+            nodes: vec![TreeNode { coord: root, parent_idx: None, cost: 0.0, children: Vec::new() }],
+            index: KdTree::from_points(vec![root]),
+        }
+    }
+
+    // Reconstrói uma árvore a partir das coordenadas e dos pais (ex.: lida de arquivo).
+    // O nó 0 deve ser a raiz; os custos são recalculados a partir dela.
+    pub fn from_parents<Sp: StateSpace<State = S>>(space: &Sp, coords: Vec<S>, parents: Vec<Option<usize>>) -> Self {
+        let index = KdTree::from_points(coords.clone());
+        let mut nodes: Vec<TreeNode<S>> = coords
+            .into_iter()
+            .zip(parents.iter())
+            .map(|(coord, &parent_idx)| TreeNode { coord, parent_idx, cost: 0.0, children: Vec::new() })
+            .collect();
+        for (i, parent_idx) in parents.iter().enumerate() {
+            if let Some(p) = *parent_idx {
+                nodes[p].children.push(i);
+            }
+        }
+
+        let mut tree = Tree { nodes, index };
+        let children = tree.nodes[0].children.clone();
+        tree.update_costs(space, children);
+        tree
+    }
+
+    pub fn add_node<Sp: StateSpace<State = S>>(&mut self, space: &Sp, coord: S, parent_idx: usize) -> usize {
+        let cost = self.nodes[parent_idx].cost + space.distance(&self.nodes[parent_idx].coord, &coord);
+        self.nodes.push(TreeNode { coord, parent_idx: Some(parent_idx), cost, children: Vec::new() });
+        let idx = self.index.insert(coord);
+        self.nodes[parent_idx].children.push(idx);
+        idx
+    }
+
+    pub fn get_node(&self, idx: usize) -> Option<&TreeNode<S>> {
+        self.nodes.get(idx)
+    }
+
+    pub fn nodes(&self) -> &Vec<TreeNode<S>> {
+        &self.nodes
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // Índice do nó mais próximo de um ponto
+    pub fn nearest<Sp: StateSpace<State = S>>(&self, space: &Sp, point: &S) -> usize {
+        self.index.nearest(space, point).map_or(0, |(i, _)| i)
+    }
+
+    // Índices dos k nós mais próximos, do mais perto para o mais longe
+    pub fn k_nearest<Sp: StateSpace<State = S>>(&self, space: &Sp, point: &S, k: usize) -> Vec<usize> {
+        self.index.k_nearest(space, point, k).into_iter().map(|(i, _)| i).collect()
+    }
+
+    // Índices de todos os nós dentro de um raio do ponto, do mais perto para o mais longe
+    pub fn near<Sp: StateSpace<State = S>>(&self, space: &Sp, point: &S, radius: f64) -> Vec<usize> {
+        self.index.within_radius(space, point, radius).into_iter().map(|(i, _)| i).collect()
+    }
+
+    // Troca o pai de um nó e propaga a variação de custo para toda a subárvore
+    pub fn rewire<Sp: StateSpace<State = S>>(&mut self, space: &Sp, idx: usize, new_parent_idx: usize) {
+        if let Some(old_parent) = self.nodes[idx].parent_idx {
+            self.nodes[old_parent].children.retain(|&c| c != idx);
+        }
+        self.nodes[idx].parent_idx = Some(new_parent_idx);
+        self.nodes[new_parent_idx].children.push(idx);
+        self.update_costs(space, vec![idx]);
+    }
+
+    fn update_costs<Sp: StateSpace<State = S>>(&mut self, space: &Sp, mut stack: Vec<usize>) {
+        while let Some(i) = stack.pop() {
+            let parent = self.nodes[i].parent_idx.unwrap();
+            self.nodes[i].cost = self.nodes[parent].cost + space.distance(&self.nodes[parent].coord, &self.nodes[i].coord);
+            stack.extend(self.nodes[i].children.iter().copied());
+        }
+    }
+
+    // Reconstrói o caminho da raiz até o nó final
+    pub fn reconstruct_path(&self, end_node_idx: usize) -> Vec<S> {
+        trace_to_root(end_node_idx, |i| self.nodes[i].parent_idx).into_iter().map(|i| self.nodes[i].coord).collect()
+    }
+}
+
+// Índices da raiz até `end`, subindo pelos pais. Serve também aos planejadores que guardam
+// os pais fora de uma Tree (FMT*, BIT*).
+pub fn trace_to_root(end: usize, parent: impl Fn(usize) -> Option<usize>) -> Vec<usize> {
+    let mut indices = vec![end];
+    while let Some(p) = parent(indices[indices.len() - 1]) {
+        indices.push(p);
+    }
+    indices.reverse();
+    indices
+}
+
+// Comprimento de um caminho na métrica do espaço
+pub fn path_length<Sp: StateSpace>(space: &Sp, path: &[Sp::State]) -> f64 {
+    path.windows(2).map(|w| space.distance(&w[0], &w[1])).sum()
+}
+
+// Esquema único dos CSVs de nós: coordenadas, coordenadas do pai e índice do pai
+// (ex.: x,y,parent_x,parent_y,parent_idx em 2D). A raiz tem pai NaN e índice -1.
+pub fn write_nodes_csv<S: State>(filename: &str, trees: &[&Tree<S>]) -> std::io::Result<()> {
+    let mut w = csv::Writer::from_path(filename)?;
+    let mut header: Vec<String> = (0..S::DIM).map(S::axis_name).collect();
+    header.extend((0..S::DIM).map(|a| format!("parent_{}", S::axis_name(a))));
+    header.push("parent_idx".to_string());
+    w.write_record(&header)?;
+
+    // Várias árvores (ex.: RRT-Connect) vão no mesmo arquivo com índices deslocados
+    let mut offset = 0;
+    for tree in trees {
+        for node in tree.nodes() {
+            let mut record: Vec<String> = node.coord.coords().iter().map(|c| c.to_string()).collect();
+            match node.parent_idx {
+                Some(p) => {
+                    record.extend(tree.nodes[p].coord.coords().iter().map(|c| c.to_string()));
+                    record.push((p + offset).to_string());
+                }
+                None => {
+                    record.extend((0..S::DIM).map(|_| "NaN".to_string()));
+                    record.push("-1".to_string());
+                }
+            }
+            w.write_record(&record)?;
+        }
+        offset += tree.len();
+    }
+    w.flush()?;
+    Ok(())
+}
+
+// Esquema único dos CSVs de caminho: só as coordenadas
+pub fn write_path_csv<S: State>(filename: &str, path: &[S]) -> std::io::Result<()> {
+    let mut w = csv::Writer::from_path(filename)?;
+    w.write_record((0..S::DIM).map(S::axis_name))?;
+    for p in path {
+        w.write_record(p.coords().iter().map(|c| c.to_string()))?;
+    }
+    w.flush()?;
+    Ok(())
+}
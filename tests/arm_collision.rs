@@ -0,0 +1,28 @@
+use sampling_path_planning::arm_collision::*;
+use sampling_path_planning::roarm::*;
+use sampling_path_planning::space::ConfigurationMap;
+
+// Um giro da base em que as duas pontas estão livres mas o meio passa pelo obstáculo:
+// o validador de arestas tem que achar a travessia, conferindo os estados intermediários
+
+const SHOULDER: f64 = 0.3;
+const ELBOW: f64 = 0.5;
+
+// Poste fino e alto exatamente onde fica a garra com a base em 0
+fn post_map(mode: ArmCollisionMode) -> ArmMap {
+    let hand = fkine_hand(&[0.0, SHOULDER, ELBOW, 0.0]);
+    ArmMap::new(vec![ArmObstacle::Cylinder { x: hand.x, y: hand.y, radius: 0.02, height: hand.z + 0.1 }]).with_collision_mode(mode)
+}
+
+#[test]
+fn finds_the_edge_that_tunnels_through_an_obstacle() {
+    let path = [[-1.2, SHOULDER, ELBOW, 0.0], [-0.8, SHOULDER, ELBOW, 0.0], [0.8, SHOULDER, ELBOW, 0.0]];
+    for mode in [ArmCollisionMode::Hand, ArmCollisionMode::Interpolated { steps: 8 }, ArmCollisionMode::Capsule { radius: 0.01 }] {
+        let map = post_map(mode);
+        assert!(path.iter().all(|q| map.is_free(q)), "{:?}: os vértices deveriam estar livres", mode);
+        // Só as pontas: a travessia passa despercebida
+        assert!(!is_path_tunneling(&map, &path, 1), "{:?}", mode);
+        assert_eq!(first_tunneling_edge(&map, &path, 20), Some(1), "{:?}", mode);
+        assert!(is_path_tunneling(&map, &path, 20));
+    }
+}
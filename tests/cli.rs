@@ -0,0 +1,44 @@
+use std::process::Command;
+
+// Códigos de saída da linha de comando: 0 sucesso, 1 erro, 2 nenhum caminho encontrado
+
+fn exit_code(args: &[&str]) -> i32 {
+    let output = Command::new(env!("CARGO_BIN_EXE_sampling-path-planning")).args(args).output().unwrap();
+    output.status.code().unwrap()
+}
+
+#[test]
+fn finds_paths_with_every_planner() {
+    for planner in ["rrt", "rrt_connect", "prm", "lazy_prm", "fmt_star", "bit_star", "kinodynamic_rrt", "sst"] {
+        let args = ["plan", "2d", "--planner", planner, "--seed", "1", "--samples", "500", "--batches", "3"];
+        assert_eq!(exit_code(&args), 0, "{}", planner);
+    }
+    for planner in ["est", "kpiece"] {
+        assert_eq!(exit_code(&["plan", "3d", "--planner", planner, "--seed", "1"]), 0, "{}", planner);
+    }
+    assert_eq!(exit_code(&["plan", "2d", "--planner", "sst", "--dynamics", "unicycle", "--max-speed", "80", "--seed", "2"]), 0);
+}
+
+#[test]
+fn reports_no_path() {
+    assert_eq!(exit_code(&["plan", "2d", "--iterations", "1", "--seed", "1"]), 2);
+}
+
+#[test]
+fn rejects_bad_arguments() {
+    let cases: [&[&str]; 10] = [
+        &[],
+        &["plan"],
+        &["plan", "4d"],
+        &["plan", "2d", "--planner", "a_star"],
+        &["plan", "2d", "--step-size", "0"],
+        &["plan", "2d", "--start", "-10,-10"],
+        &["plan", "arm", "--planner", "prm"],
+        &["plan", "3d", "--planner", "sst", "--dynamics", "unicycle"],
+        &["plan", "2d", "--planner", "fmt_star", "--nodes", "nodes.csv"],
+        &["benchmark", "2d", "--runs", "0"],
+    ];
+    for args in cases {
+        assert_eq!(exit_code(args), 1, "{:?}", args);
+    }
+}
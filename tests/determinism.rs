@@ -0,0 +1,63 @@
+use sampling_path_planning::planner::TreePlanner;
+use sampling_path_planning::prm::PRMPlanner;
+use sampling_path_planning::readmap::OccupancyMap;
+use sampling_path_planning::rrt_connect::RRTConnectPlanner;
+use sampling_path_planning::structs::Point;
+
+// Mesma semente e mesmo mapa reproduzem exatamente a árvore e o caminho; sementes
+// diferentes dão resultados diferentes
+
+const MAP: &str = "data/map.jpg";
+const START: Point = Point { x: 50.0, y: 50.0 };
+const GOAL: Point = Point { x: 700.0, y: 500.0 };
+
+// Coordenada e pai de cada nó da árvore, e o caminho
+type TreeRun = (Vec<(Point, Option<usize>)>, Option<Vec<Point>>);
+
+fn tree_planner_run(seed: u64) -> TreeRun {
+    let mut planner = TreePlanner::new(START, GOAL, OccupancyMap::new(MAP), 15.0, 10.0).with_max_iter(3000).with_seed(seed);
+    let path = planner.plan_star().cloned();
+    let tree = planner.tree().nodes().iter().map(|n| (n.coord, n.parent_idx)).collect();
+    (tree, path)
+}
+
+fn prm_run(seed: u64) -> Option<Vec<Point>> {
+    let mut planner = PRMPlanner::new(OccupancyMap::new(MAP), 300, 10).with_seed(seed);
+    planner.query(START, GOAL)
+}
+
+fn rrt_connect_run(seed: u64) -> (usize, Option<Vec<Point>>) {
+    let mut planner = RRTConnectPlanner::new(START, GOAL, OccupancyMap::new(MAP), 15.0, 5000, 10).with_seed(seed);
+    let path = planner.plan().cloned();
+    (planner.node_count(), path)
+}
+
+#[test]
+fn tree_planner_is_reproducible() {
+    let first = tree_planner_run(7);
+    assert!(first.1.is_some(), "RRT* deveria achar um caminho");
+    assert_eq!(first, tree_planner_run(7));
+    assert_ne!(first, tree_planner_run(8));
+}
+
+#[test]
+fn prm_is_reproducible() {
+    let first = prm_run(7);
+    assert!(first.is_some(), "PRM deveria achar um caminho");
+    assert_eq!(first, prm_run(7));
+    assert_ne!(first, prm_run(8));
+}
+
+#[test]
+fn rrt_connect_is_reproducible() {
+    let first = rrt_connect_run(7);
+    assert!(first.1.is_some(), "RRT-Connect deveria achar um caminho");
+    assert_eq!(first, rrt_connect_run(7));
+    assert_ne!(first, rrt_connect_run(8));
+}
+
+#[test]
+#[should_panic(expected = "passo do RRT-Connect")]
+fn rrt_connect_rejects_non_positive_step() {
+    RRTConnectPlanner::new(START, GOAL, OccupancyMap::new(MAP), 0.0, 100, 10);
+}
@@ -0,0 +1,76 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use sampling_path_planning::distance_field::{DistanceField, MedialAxisSampler};
+
+// A transformada separável deve dar a distância euclidiana exata até a célula ocupada
+// mais próxima, inclusive com espaçamentos diferentes por eixo
+
+// Coordenadas de cada célula, no mesmo índice plano de DistanceField::values
+fn cell_coords(dims: &[usize], spacing: &[f64]) -> Vec<Vec<f64>> {
+    let total: usize = dims.iter().product();
+    (0..total)
+        .map(|mut idx| {
+            dims.iter()
+                .zip(spacing)
+                .map(|(&n, &h)| {
+                    let c = idx % n;
+                    idx /= n;
+                    c as f64 * h
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn check_against_brute_force(dims: Vec<usize>, spacing: Vec<f64>, density: f64, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let total: usize = dims.iter().product();
+    let mut occupied: Vec<bool> = (0..total).map(|_| rng.r#gen::<f64>() < density).collect();
+    occupied[0] = true;
+
+    let coords = cell_coords(&dims, &spacing);
+    let obstacles: Vec<&Vec<f64>> = coords.iter().zip(&occupied).filter(|(_, o)| **o).map(|(c, _)| c).collect();
+    let field = DistanceField::from_occupancy(dims, spacing, &occupied);
+
+    for (c, &value) in coords.iter().zip(field.values()) {
+        let expected = obstacles
+            .iter()
+            .map(|o| o.iter().zip(c).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt())
+            .fold(f64::INFINITY, f64::min);
+        assert!((value - expected).abs() < 1e-9, "célula {:?}: {} != {}", c, value, expected);
+    }
+}
+
+#[test]
+fn edt_2d_matches_brute_force() {
+    check_against_brute_force(vec![40, 30], vec![1.0, 1.0], 0.05, 1);
+    check_against_brute_force(vec![25, 35], vec![1.0, 2.5], 0.02, 2);
+}
+
+#[test]
+fn edt_3d_matches_brute_force() {
+    check_against_brute_force(vec![15, 12, 8], vec![1.0, 1.0, 3.0], 0.03, 3);
+}
+
+#[test]
+fn single_obstacle_gives_distance_to_it() {
+    let mut occupied = vec![false; 9 * 9];
+    occupied[4 + 9 * 4] = true;
+    let field = DistanceField::from_occupancy(vec![9, 9], vec![1.0, 1.0], &occupied);
+    assert_eq!(field.clearance(&[4.0, 4.0]), 0.0);
+    assert_eq!(field.clearance(&[7.0, 4.0]), 3.0);
+    assert_eq!(field.clearance(&[0.0, 0.0]), 32f64.sqrt());
+}
+
+#[test]
+fn retraction_follows_the_gradient_to_the_center() {
+    // Sala quadrada fechada: o eixo medial são as diagonais, e a partir de (5, 3) a
+    // folga primeiro cresce em y e depois ao longo da diagonal até o centro
+    let n = 41;
+    let occupied: Vec<bool> = (0..n * n).map(|i| i % n == 0 || i % n == n - 1 || i / n == 0 || i / n == n - 1).collect();
+    let field = DistanceField::from_occupancy(vec![n, n], vec![1.0, 1.0], &occupied);
+    let sampler = MedialAxisSampler::new(field.clone());
+    let retracted = sampler.retract(&[5.0, 3.0]);
+    assert!(field.clearance(&retracted) >= 18.0, "{:?}", retracted);
+}
@@ -0,0 +1,61 @@
+use sampling_path_planning::geometry::Primitive;
+use sampling_path_planning::structs3d::Point3;
+
+// Distâncias dos primitivos contra casos de forma fechada
+
+fn p(x: f64, y: f64, z: f64) -> Point3 {
+    Point3 { x, y, z }
+}
+
+fn assert_close(found: f64, expected: f64) {
+    assert!((found - expected).abs() < 1e-9, "{} != {}", found, expected);
+}
+
+#[test]
+fn point_distances() {
+    let cylinder = Primitive::Cylinder { base: p(0.0, 0.0, 0.0), top: p(0.0, 0.0, 4.0), radius: 1.0 };
+    assert_close(cylinder.distance(&p(3.0, 0.0, 2.0)), 2.0); // Lateral
+    assert_close(cylinder.distance(&p(0.0, 0.0, 7.0)), 3.0); // Acima da tampa
+    assert_close(cylinder.distance(&p(4.0, 0.0, 8.0)), 5.0); // Quina: hypot(3, 4)
+    assert_close(cylinder.distance(&p(0.5, 0.0, 1.0)), 0.0);
+
+    let aabb = Primitive::Aabb { min: p(0.0, 0.0, 0.0), max: p(1.0, 1.0, 1.0) };
+    assert_close(aabb.distance(&p(2.0, 3.0, 1.0)), 5.0_f64.sqrt());
+
+    // Cubo de lado 2 girado 45° em z: (2, 0, 0) fica a (√2 - 1) de duas faces locais
+    let obb = Primitive::oriented_box(p(0.0, 0.0, 0.0), p(2.0, 2.0, 2.0), 0.0, 0.0, std::f64::consts::FRAC_PI_4);
+    assert_close(obb.distance(&p(2.0, 0.0, 0.0)), 2.0 - 2.0_f64.sqrt());
+}
+
+#[test]
+fn segment_distances_to_spheres_and_capsules() {
+    let sphere = Primitive::Sphere { center: p(0.0, 0.0, 0.0), radius: 1.0 };
+    assert_close(sphere.segment_distance(&p(-5.0, 2.0, 0.0), &p(5.0, 2.0, 0.0)), 1.0);
+    assert_close(sphere.segment_distance(&p(3.0, 0.0, 0.0), &p(6.0, 0.0, 0.0)), 2.0);
+
+    let capsule = Primitive::Capsule { a: p(0.0, 0.0, 0.0), b: p(10.0, 0.0, 0.0), radius: 1.0 };
+    // Segmento paralelo deslocado
+    assert_close(capsule.segment_distance(&p(2.0, 3.0, 0.0), &p(8.0, 3.0, 0.0)), 2.0);
+    // Segmento reverso cruzando acima do eixo
+    assert_close(capsule.segment_distance(&p(5.0, -4.0, 5.0), &p(5.0, 4.0, 5.0)), 4.0);
+    // Colinear, além da ponta
+    assert_close(capsule.segment_distance(&p(12.0, 0.0, 0.0), &p(15.0, 0.0, 0.0)), 1.0);
+    assert_close(capsule.segment_distance(&p(5.0, -4.0, 0.5), &p(5.0, 4.0, 0.5)), 0.0);
+}
+
+#[test]
+fn segment_distances_to_cylinders() {
+    let cylinder = Primitive::Cylinder { base: p(0.0, 0.0, 0.0), top: p(0.0, 0.0, 4.0), radius: 1.0 };
+    // Passa ao lado, a 3 do eixo
+    assert_close(cylinder.segment_distance(&p(3.0, -5.0, 2.0), &p(3.0, 5.0, 2.0)), 2.0);
+    // Paralelo ao eixo
+    assert_close(cylinder.segment_distance(&p(0.0, 2.5, 1.0), &p(0.0, 2.5, 3.0)), 1.5);
+    // Acima da tampa
+    assert_close(cylinder.segment_distance(&p(-5.0, 0.0, 6.0), &p(5.0, 0.0, 6.0)), 2.0);
+    // Ponto mais próximo na quina da tampa
+    assert_close(cylinder.segment_distance(&p(2.0, 0.0, 5.0), &p(5.0, 0.0, 9.0)), 2.0_f64.sqrt());
+    // Atravessa o cilindro
+    assert_close(cylinder.segment_distance(&p(-5.0, 0.0, 2.0), &p(5.0, 0.0, 2.0)), 0.0);
+    assert!(cylinder.segment_intersects(&p(-5.0, 0.0, 2.0), &p(5.0, 0.0, 2.0)));
+    assert!(!cylinder.segment_intersects(&p(3.0, -5.0, 2.0), &p(3.0, 5.0, 2.0)));
+}
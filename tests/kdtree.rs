@@ -0,0 +1,75 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use sampling_path_planning::kdtree::KdTree;
+use sampling_path_planning::se2::{Pose2, SE2Space};
+use sampling_path_planning::space::*;
+use sampling_path_planning::structs::Point;
+use sampling_path_planning::structs3d::Point3;
+
+// As consultas do KD-tree devem dar as mesmas distâncias que a busca exaustiva,
+// tanto no espaço euclidiano quanto em SE(2), onde θ dá a volta em ±π
+
+fn brute_force<Sp: StateSpace>(space: &Sp, points: &[Sp::State], query: &Sp::State) -> Vec<f64> {
+    let mut d: Vec<f64> = points.iter().map(|p| space.distance(query, p)).collect();
+    d.sort_by(f64::total_cmp);
+    d
+}
+
+// Iguais a menos de arredondamento (a métrica de SE(2) não é simétrica bit a bit)
+fn assert_close(found: &[f64], expected: &[f64]) {
+    assert_eq!(found.len(), expected.len());
+    for (a, b) in found.iter().zip(expected) {
+        assert!((a - b).abs() <= 1e-9, "{} != {}", a, b);
+    }
+}
+
+fn check_queries<Sp: StateSpace>(space: &Sp, index: &KdTree<Sp::State>, queries: &[Sp::State]) {
+    for query in queries {
+        let expected = brute_force(space, index.points(), query);
+
+        let (idx, d) = index.nearest(space, query).unwrap();
+        assert_close(&[d, space.distance(query, &index.points()[idx])], &[expected[0], expected[0]]);
+
+        let k: Vec<f64> = index.k_nearest(space, query, 10).into_iter().map(|(_, d)| d).collect();
+        assert_close(&k, &expected[..10]);
+
+        // Raio entre duas distâncias, para não depender do arredondamento na borda
+        let radius = (expected[25] + expected[26]) / 2.0;
+        let within: Vec<f64> = index.within_radius(space, query, radius).into_iter().map(|(_, d)| d).collect();
+        assert_close(&within, &expected[..26]);
+    }
+}
+
+#[test]
+fn euclidean_queries_match_brute_force() {
+    let space = EuclideanSpace::new(Point3 { x: 0.0, y: 0.0, z: 0.0 }, Point3 { x: 100.0, y: 50.0, z: 10.0 });
+    let mut rng = StdRng::seed_from_u64(1);
+    let points: Vec<Point3> = (0..500).map(|_| space.sample_uniform(&mut rng)).collect();
+    let queries: Vec<Point3> = (0..100).map(|_| space.sample_uniform(&mut rng)).collect();
+
+    check_queries(&space, &KdTree::from_points(points.clone()), &queries);
+
+    // Inserção um a um, passando pelas reconstruções parciais
+    let mut index = KdTree::new();
+    for p in points {
+        index.insert(p);
+    }
+    check_queries(&space, &index, &queries);
+}
+
+#[test]
+fn se2_queries_match_brute_force() {
+    let space = SE2Space::new(Point { x: 0.0, y: 0.0 }, Point { x: 200.0, y: 200.0 }, 30.0);
+    let mut rng = StdRng::seed_from_u64(2);
+    let points: Vec<Pose2> = (0..500).map(|_| space.sample_uniform(&mut rng)).collect();
+    let mut queries: Vec<Pose2> = (0..100).map(|_| space.sample_uniform(&mut rng)).collect();
+    // Consultas perto de ±π, onde a poda por θ estaria errada
+    queries.extend((0..20).map(|i| Pose2 { x: 10.0 * i as f64, y: 100.0, theta: if i % 2 == 0 { 3.1 } else { -3.1 } }));
+
+    let mut index = KdTree::new();
+    for p in points {
+        index.insert(p);
+    }
+    check_queries(&space, &index, &queries);
+}
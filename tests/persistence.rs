@@ -0,0 +1,157 @@
+use sampling_path_planning::planner::TreePlanner;
+use sampling_path_planning::prm::PRMPlanner;
+use sampling_path_planning::space::*;
+use sampling_path_planning::structs::Point;
+
+// Árvore e roadmap salvos e carregados de novo: mesmos nós, pais, arestas e custos.
+// Arquivos de outro mapa ou com a estrutura corrompida são recusados.
+
+const START: Point = Point { x: 5.0, y: 5.0 };
+const GOAL: Point = Point { x: 95.0, y: 95.0 };
+
+type Map = PredicateMap<EuclideanSpace<Point>, fn(&Point) -> bool>;
+
+// Quadrado 100 x 100 com uma parede no meio, aberta em cima
+fn wall_map(name: &str) -> Map {
+    let space = EuclideanSpace::new(Point { x: 0.0, y: 0.0 }, Point { x: 100.0, y: 100.0 });
+    PredicateMap::new(space, name, |p: &Point| !((45.0..55.0).contains(&p.x) && p.y < 80.0))
+}
+
+fn planner(name: &str) -> TreePlanner<Map> {
+    TreePlanner::new(START, GOAL, wall_map(name), 5.0, 3.0).with_max_iter(1500)
+}
+
+fn temp_file(name: &str) -> String {
+    std::env::temp_dir().join(format!("spp-{}-{}.csv", name, std::process::id())).to_string_lossy().into_owned()
+}
+
+fn saved_tree(file: &str) -> TreePlanner<Map> {
+    let mut original = planner("parede").with_goal_bias(0.2).with_collision_steps(7).with_seed(3);
+    original.plan_star();
+    original.save_tree(file).unwrap();
+    original
+}
+
+#[test]
+fn tree_round_trip() {
+    let file = temp_file("round-trip");
+    let original = saved_tree(&file);
+
+    let mut loaded = planner("parede");
+    loaded.load_tree(&file).unwrap();
+    std::fs::remove_file(&file).ok();
+
+    let (a, b) = (original.tree().nodes(), loaded.tree().nodes());
+    assert_eq!(a.len(), b.len());
+    for (x, y) in a.iter().zip(b) {
+        assert_eq!(x.coord, y.coord);
+        assert_eq!(x.parent_idx, y.parent_idx);
+        assert!((x.cost - y.cost).abs() < 1e-9);
+    }
+
+    // Os parâmetros voltam junto: o mesmo arquivo salvo de novo é idêntico
+    let again = temp_file("round-trip-again");
+    saved_tree(&file);
+    loaded.save_tree(&again).unwrap();
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), std::fs::read_to_string(&again).unwrap());
+    std::fs::remove_file(&file).ok();
+    std::fs::remove_file(&again).ok();
+}
+
+#[test]
+fn rejects_tree_from_another_map() {
+    let file = temp_file("other-map");
+    saved_tree(&file);
+    let result = planner("outro").load_tree(&file);
+    std::fs::remove_file(&file).ok();
+    assert!(result.is_err());
+}
+
+// Substitui a aresta que chega em `child` por uma vinda de `parent`
+fn with_edge(text: &str, child: usize, parent: usize) -> String {
+    text.lines()
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            if fields[0] == "edge" && fields[2] == child.to_string() {
+                format!("edge,{},{},{}", parent, child, fields[3])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn rejects_corrupted_trees() {
+    let file = temp_file("corrupted");
+    saved_tree(&file);
+    let text = std::fs::read_to_string(&file).unwrap();
+
+    let cases = [
+        // 1 e 2 como pais um do outro: nenhum dos dois chega à raiz
+        with_edge(&with_edge(&text, 1, 2), 2, 1),
+        // Nó que é pai de si mesmo
+        with_edge(&text, 3, 3),
+        // Aresta entrando na raiz
+        format!("{}\nedge,1,0,1.0", text),
+        // Dois pais para o mesmo nó
+        format!("{}\nedge,0,4,1.0", text),
+        // Sem goal_bias
+        text.lines().filter(|l| !l.contains("goal_bias")).collect::<Vec<_>>().join("\n"),
+    ];
+    for (i, case) in cases.iter().enumerate() {
+        std::fs::write(&file, case).unwrap();
+        assert!(planner("parede").load_tree(&file).is_err(), "caso {} deveria ser recusado", i);
+    }
+    std::fs::remove_file(&file).ok();
+}
+
+fn saved_roadmap(file: &str) -> PRMPlanner<Map> {
+    let mut prm = PRMPlanner::new(wall_map("parede"), 300, 10).with_lazy(true).with_seed(4);
+    prm.build_roadmap();
+    prm.save_roadmap(file).unwrap();
+    prm
+}
+
+#[test]
+fn roadmap_round_trip() {
+    let file = temp_file("roadmap");
+    let mut original = saved_roadmap(&file);
+    let mut loaded = PRMPlanner::new(wall_map("parede"), 10, 1);
+    loaded.load_roadmap(&file).unwrap();
+    std::fs::remove_file(&file).ok();
+
+    assert_eq!(original.nodes, loaded.nodes);
+    assert_eq!(original.num_edges(), loaded.num_edges());
+    // Mesmos parâmetros (inclusive lazy): a mesma consulta dá o mesmo caminho
+    let path = original.query(START, GOAL);
+    assert!(path.is_some());
+    assert_eq!(path, loaded.query(START, GOAL));
+}
+
+#[test]
+fn rejects_corrupted_roadmaps() {
+    let file = temp_file("roadmap-corrupted");
+    saved_roadmap(&file);
+    let text = std::fs::read_to_string(&file).unwrap();
+    let first_edge = text.lines().find(|l| l.starts_with("edge,")).unwrap().to_string();
+    let fields: Vec<&str> = first_edge.split(',').collect();
+
+    let cases = [
+        // Sem o parâmetro lazy
+        text.lines().filter(|l| !l.contains("lazy")).collect::<Vec<_>>().join("\n"),
+        // Laço
+        format!("{}\nedge,5,5,0.0", text),
+        // Aresta repetida, nos dois sentidos
+        format!("{}\n{}", text, first_edge),
+        format!("{}\nedge,{},{},{}", text, fields[2], fields[1], fields[3]),
+    ];
+    for (i, case) in cases.iter().enumerate() {
+        std::fs::write(&file, case).unwrap();
+        let mut prm = PRMPlanner::new(wall_map("parede"), 10, 1);
+        assert!(prm.load_roadmap(&file).is_err(), "caso {} deveria ser recusado", i);
+        assert!(prm.nodes.is_empty());
+    }
+    std::fs::remove_file(&file).ok();
+}
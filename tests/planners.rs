@@ -0,0 +1,110 @@
+use std::f64::consts::PI;
+
+use sampling_path_planning::dynamics::{DoubleIntegrator2D, Dynamics, PhaseState};
+use sampling_path_planning::kinodynamic::KinodynamicPlanner;
+use sampling_path_planning::planner::TreePlanner;
+use sampling_path_planning::prm::PRMPlanner;
+use sampling_path_planning::space::*;
+use sampling_path_planning::sst::SSTPlanner;
+use sampling_path_planning::structs::Point;
+
+// Propriedades de comportamento dos planejadores, além da reprodutibilidade
+
+type Map = PredicateMap<EuclideanSpace<Point>, fn(&Point) -> bool>;
+
+fn free_map(size: f64) -> Map {
+    let space = EuclideanSpace::new(Point { x: 0.0, y: 0.0 }, Point { x: size, y: size });
+    PredicateMap::new(space, "livre", |_: &Point| true)
+}
+
+// Quadrado 100 x 100 com uma parede no meio, aberta em cima
+fn wall_map() -> Map {
+    let space = EuclideanSpace::new(Point { x: 0.0, y: 0.0 }, Point { x: 100.0, y: 100.0 });
+    PredicateMap::new(space, "parede", |p: &Point| !((45.0..55.0).contains(&p.x) && p.y < 80.0))
+}
+
+fn tree_planner(seed: u64) -> TreePlanner<Map> {
+    TreePlanner::new(Point { x: 5.0, y: 5.0 }, Point { x: 95.0, y: 5.0 }, wall_map(), 5.0, 3.0).with_max_iter(3000).with_seed(seed)
+}
+
+// Com a mesma semente o RRT* sorteia as mesmas amostras e cria os mesmos nós que o RRT,
+// só que com pais de custo menor ou igual, então nunca termina com um caminho pior
+#[test]
+fn rrt_star_is_never_worse_than_rrt() {
+    for seed in 0..10 {
+        let mut rrt = tree_planner(seed);
+        rrt.plan();
+        let mut rrt_star = tree_planner(seed);
+        rrt_star.plan_star();
+        let (Some(a), Some(b)) = (rrt.path_cost(), rrt_star.path_cost()) else {
+            panic!("semente {}: os dois deveriam achar caminho", seed);
+        };
+        assert!(b <= a + 1e-9, "semente {}: RRT* {} > RRT {}", seed, b, a);
+    }
+}
+
+// O Lazy PRM só verifica as arestas dos caminhos candidatos; o PRM verifica todas ao
+// montar o roadmap. Os dois verificam os mesmos nós e acham caminho.
+#[test]
+fn lazy_prm_checks_fewer_edges() {
+    let (start, goal) = (Point { x: 5.0, y: 5.0 }, Point { x: 95.0, y: 5.0 });
+    let mut eager = PRMPlanner::new(wall_map(), 300, 10).with_seed(2);
+    let mut lazy = PRMPlanner::new(wall_map(), 300, 10).with_lazy(true).with_seed(2);
+    assert!(eager.query(start, goal).is_some());
+    assert!(lazy.query(start, goal).is_some());
+
+    let (e, l) = (eager.checks(), lazy.checks());
+    assert_eq!(e.node_checks, l.node_checks);
+    assert!(l.edge_checks * 5 < e.edge_checks, "lazy {} arestas, eager {}", l.edge_checks, e.edge_checks);
+}
+
+// SST com objetivo inalcançável (raio 0), rodando todas as iterações
+fn sst_run(iterations: u32) -> (usize, usize) {
+    let start = PhaseState { position: Point { x: 1.0, y: 1.0 }, velocity: Point { x: 0.0, y: 0.0 } };
+    let goal = PhaseState { position: Point { x: 9.0, y: 9.0 }, velocity: Point { x: 0.0, y: 0.0 } };
+    let mut sst = SSTPlanner::new(start, goal, free_map(10.0), DoubleIntegrator2D::new(2.0, 1.0), 0.1, 0.0)
+        .with_witness_radius(1.0)
+        .with_selection_radius(2.0)
+        .with_max_iter(iterations)
+        .with_seed(1);
+    sst.plan();
+    (sst.node_count(), sst.witness_count())
+}
+
+#[test]
+fn sst_memory_stays_bounded() {
+    let (nodes_short, _) = sst_run(5_000);
+    let (nodes_long, witnesses) = sst_run(20_000);
+    // As testemunhas ficam a pelo menos 1.0 umas das outras: no máximo o volume do espaço
+    // de fase (10 x 10 x 4 x 4, com meia testemunha de folga) sobre o de uma bola 4D de raio 0.5
+    let packing = 11.0 * 11.0 * 5.0 * 5.0 / (PI * PI / 2.0 * 0.5f64.powi(4));
+    assert!((witnesses as f64) < packing, "{} testemunhas", witnesses);
+    // A árvore cresce bem menos que o número de iterações (o RRT cinodinâmico guardaria todas)
+    assert!(nodes_long < 20_000 / 10, "{} nós", nodes_long);
+    assert!(nodes_long < 5 * nodes_short / 2, "{} nós em 5000 iterações, {} em 20000", nodes_short, nodes_long);
+}
+
+// A trajetória do RRT cinodinâmico é reintegrada passo a passo com os controles
+// guardados: respeita os limites do modelo, não colide e termina perto do objetivo
+#[test]
+fn kinodynamic_trajectory_follows_the_dynamics() {
+    let dynamics = DoubleIntegrator2D::new(10.0, 5.0);
+    let start = PhaseState { position: Point { x: 5.0, y: 5.0 }, velocity: Point { x: 0.0, y: 0.0 } };
+    let goal = PhaseState { position: Point { x: 95.0, y: 5.0 }, velocity: Point { x: 0.0, y: 0.0 } };
+    let mut planner = KinodynamicPlanner::new(start, goal, wall_map(), dynamics, 0.1, 5.0).with_seed(1);
+    let trajectory = planner.plan().expect("sem trajetória").clone();
+
+    assert_eq!(trajectory[0].state, start);
+    assert!(trajectory.last().unwrap().state.position.dist(&goal.position) <= 5.0);
+    let map = wall_map();
+    for w in trajectory.windows(2) {
+        let control = w[0].control.expect("controle ausente no meio da trajetória");
+        assert!(control.x.abs() <= 5.0 && control.y.abs() <= 5.0, "{:?}", control);
+        assert!((w[1].time - w[0].time - 0.1).abs() < 1e-9);
+        let next = dynamics.propagate(&w[0].state, &control, 0.1);
+        assert!(next.coords().iter().zip(w[1].state.coords()).all(|(a, b)| (a - b).abs() < 1e-9));
+        assert!(dynamics.is_valid(&w[1].state) && map.is_free(&w[1].state.position));
+    }
+    assert_eq!(trajectory.last().unwrap().control, None);
+    assert_eq!(planner.duration(), Some(trajectory.last().unwrap().time));
+}
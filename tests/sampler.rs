@@ -0,0 +1,45 @@
+use sampling_path_planning::sampler::{HaltonSampler, SobolSampler};
+
+// Prefixos das sequências de baixa discrepância contra valores de referência
+// (Halton nas bases 2, 3 e 5; Sobol com os números de direção de Joe e Kuo, sem o ponto 0)
+
+fn assert_prefix(found: Vec<Vec<f64>>, expected: &[[f64; 3]]) {
+    for (i, (p, q)) in found.iter().zip(expected).enumerate() {
+        assert!(p.iter().zip(q).all(|(a, b)| (a - b).abs() < 1e-12), "ponto {}: {:?} != {:?}", i, p, q);
+    }
+}
+
+#[test]
+fn halton_prefix_matches_reference() {
+    let mut halton = HaltonSampler::new();
+    let found = (0..6).map(|_| halton.next_unit(3)).collect();
+    assert_prefix(
+        found,
+        &[
+            [1.0 / 2.0, 1.0 / 3.0, 1.0 / 5.0],
+            [1.0 / 4.0, 2.0 / 3.0, 2.0 / 5.0],
+            [3.0 / 4.0, 1.0 / 9.0, 3.0 / 5.0],
+            [1.0 / 8.0, 4.0 / 9.0, 4.0 / 5.0],
+            [5.0 / 8.0, 7.0 / 9.0, 1.0 / 25.0],
+            [3.0 / 8.0, 2.0 / 9.0, 6.0 / 25.0],
+        ],
+    );
+}
+
+#[test]
+fn sobol_prefix_matches_reference() {
+    let mut sobol = SobolSampler::new();
+    let found = (0..7).map(|_| sobol.next_unit(3)).collect();
+    assert_prefix(
+        found,
+        &[
+            [0.5, 0.5, 0.5],
+            [0.75, 0.25, 0.25],
+            [0.25, 0.75, 0.75],
+            [0.375, 0.375, 0.625],
+            [0.875, 0.875, 0.125],
+            [0.625, 0.125, 0.875],
+            [0.125, 0.625, 0.375],
+        ],
+    );
+}
@@ -0,0 +1,154 @@
+use std::io::ErrorKind;
+
+use sampling_path_planning::scene::Scene;
+
+// Cenas com entradas inválidas são recusadas na leitura, com erro e sem pânico
+
+const PRIMITIVES: &str = r#"
+start = [1.0, 1.0, 1.0]
+goal = [9.0, 1.0, 1.0]
+
+[map]
+type = "primitives"
+lower = [0.0, 0.0, 0.0]
+upper = [10.0, 10.0, 5.0]
+obstacles = [OBSTACLE]
+"#;
+
+const IMAGE: &str = r#"
+start = [50.0, 50.0]
+goal = [700.0, 500.0]
+
+[map]
+type = "image"
+path = "data/map.jpg"
+"#;
+
+const REPLICATED: &str = r#"
+start = [50.0, 50.0, 1.0]
+goal = [700.0, 500.0, 5.0]
+
+[map]
+type = "replicated"
+path = "data/map.jpg"
+depth = 10
+dz = 1.0
+"#;
+
+const ARM: &str = r#"
+start = [0.0, 0.0, 0.0, 0.0]
+goal = [0.2, 0.2, 0.3]
+
+[map]
+type = "arm"
+obstacles = [{ type = "cylinder", x = 0.3, y = 0.0, r = 0.05, h = 0.3 }]
+"#;
+
+fn with_obstacle(obstacle: &str) -> String {
+    PRIMITIVES.replace("OBSTACLE", obstacle)
+}
+
+fn with_robot(scene: &str, robot: &str) -> String {
+    format!("{}\n[robot]\n{}\n", scene, robot)
+}
+
+fn error_kind(text: &str) -> Option<ErrorKind> {
+    Scene::from_toml_str(text).err().map(|e| e.kind())
+}
+
+#[test]
+fn accepts_valid_scenes() {
+    assert!(Scene::from_toml_str(&with_obstacle(r#"{ type = "sphere", center = [5.0, 5.0, 2.0], radius = 1.0 }"#)).is_ok());
+    assert!(Scene::from_toml_str(&with_robot(IMAGE, "type = \"rectangle\"\nlength = 20.0\nwidth = 10.0")).is_ok());
+    assert!(Scene::from_toml_str(&with_robot(REPLICATED, "type = \"disk\"\nradius = 3.0")).is_ok());
+    assert!(Scene::from_toml_str(&with_robot(ARM, "type = \"arm\"\ncollision = \"capsule\"\nlink_radius = 0.02")).is_ok());
+    for file in ["scenes/map2d.toml", "scenes/map3d.toml", "scenes/car2d.toml", "scenes/primitives.toml", "scenes/arm_ctrl.toml", "scenes/arm_dense.json"] {
+        assert!(Scene::load(file).is_ok(), "{} deveria carregar", file);
+    }
+}
+
+#[test]
+fn rejects_unknown_keys() {
+    assert_eq!(error_kind(&format!("{}\nfoo = 1\n", IMAGE)), Some(ErrorKind::InvalidData));
+    assert_eq!(error_kind(&with_robot(IMAGE, "type = \"disk\"\nradius = 1.0\ncolor = \"red\"")), Some(ErrorKind::InvalidData));
+    assert_eq!(error_kind(&format!("{}\n[planner]\nstep = 1.0\n", IMAGE)), Some(ErrorKind::InvalidData));
+    assert_eq!(error_kind(&with_robot(IMAGE, "type = \"tank\"")), Some(ErrorKind::InvalidData));
+}
+
+#[test]
+fn rejects_unsupported_robot_and_map_pairs() {
+    let rectangle = "type = \"rectangle\"\nlength = 3000.0\nwidth = 3000.0";
+    let polygon = "type = \"polygon\"\nvertices = [[0.0, 0.0], [10.0, 0.0], [0.0, 10.0]]";
+    let arm = "type = \"arm\"";
+    let disk = "type = \"disk\"\nradius = 0.01";
+    let cases = [
+        with_robot(REPLICATED, rectangle),
+        with_robot(REPLICATED, polygon),
+        with_robot(&with_obstacle(""), rectangle),
+        with_robot(&with_obstacle(""), polygon),
+        with_robot(IMAGE, arm),
+        with_robot(REPLICATED, arm),
+        with_robot(ARM, rectangle),
+        with_robot(ARM, disk),
+    ];
+    for case in &cases {
+        assert_eq!(error_kind(case), Some(ErrorKind::InvalidInput), "cena deveria ser recusada:\n{}", case);
+    }
+}
+
+#[test]
+fn rejects_degenerate_robots() {
+    let cases = [
+        "type = \"rectangle\"\nlength = 0.0\nwidth = 0.0",
+        "type = \"rectangle\"\nlength = 20.0\nwidth = -1.0",
+        "type = \"polygon\"\nvertices = [[0.0, 0.0], [10.0, 0.0]]",
+        "type = \"polygon\"\nvertices = [[0.0, 0.0], [10.0, 0.0], [20.0, 0.0]]",
+        "type = \"disk\"\nradius = -2.0",
+    ];
+    for robot in cases {
+        assert_eq!(error_kind(&with_robot(IMAGE, robot)), Some(ErrorKind::InvalidInput), "robô deveria ser recusado: {}", robot);
+    }
+    assert_eq!(error_kind(&with_robot(ARM, "type = \"arm\"\nlink_steps = 0")), Some(ErrorKind::InvalidInput));
+}
+
+#[test]
+fn rejects_degenerate_obstacles() {
+    let cases = [
+        r#"{ type = "sphere", center = [5.0, 5.0, 2.0], radius = -1.0 }"#,
+        r#"{ type = "sphere", center = [5.0, 5.0, 2.0], radius = 0.0 }"#,
+        r#"{ type = "aabb", min = [4.0, 0.0, 0.0], max = [4.0, 9.0, 5.0] }"#,
+        r#"{ type = "aabb", min = [4.0, 0.0, 0.0], max = [6.0, -1.0, 5.0] }"#,
+        r#"{ type = "obb", center = [8.0, 3.0, 2.5], size = [3.0, 0.0, 5.0] }"#,
+        r#"{ type = "cylinder", base = [2.0, 5.0, 0.0], top = [2.0, 5.0, 5.0], radius = 0.0 }"#,
+        r#"{ type = "cylinder", base = [2.0, 5.0, 1.0], top = [2.0, 5.0, 1.0], radius = 1.0 }"#,
+        r#"{ type = "capsule", a = [1.0, 1.0, 1.0], b = [2.0, 2.0, 2.0], radius = -0.5 }"#,
+    ];
+    for obstacle in cases {
+        assert_eq!(error_kind(&with_obstacle(obstacle)), Some(ErrorKind::InvalidInput), "obstáculo deveria ser recusado: {}", obstacle);
+    }
+    let flat_box = ARM.replace(r#"{ type = "cylinder", x = 0.3, y = 0.0, r = 0.05, h = 0.3 }"#, r#"{ type = "box", x = 0.3, y = 0.0, size_x = 0.1, size_y = 0.1, size_z = 0.0 }"#);
+    assert_eq!(error_kind(&flat_box), Some(ErrorKind::InvalidInput));
+}
+
+#[test]
+fn rejects_out_of_range_parameters() {
+    for planner in ["step_size = 0.0", "step_size = -1.0", "goal_radius = -1.0", "goal_bias = 1.5", "collision_steps = 0"] {
+        assert_eq!(error_kind(&format!("{}\n[planner]\n{}\n", IMAGE, planner)), Some(ErrorKind::InvalidInput), "{}", planner);
+    }
+    assert_eq!(error_kind(&REPLICATED.replace("dz = 1.0", "dz = 0.0")), Some(ErrorKind::InvalidInput));
+    assert_eq!(error_kind(&REPLICATED.replace("depth = 10", "depth = 0")), Some(ErrorKind::InvalidInput));
+}
+
+#[test]
+fn reports_colliding_arm_start() {
+    let blocked = ARM.replace(r#"{ type = "cylinder", x = 0.3, y = 0.0, r = 0.05, h = 0.3 }"#, r#"{ type = "box", x = 0.0, y = 0.0, size_x = 1.0, size_y = 1.0, size_z = 1.0 }"#);
+    let scene = Scene::from_toml_str(&blocked).unwrap();
+    assert_eq!(scene.solve().err().map(|e| e.kind()), Some(ErrorKind::InvalidInput));
+}
+
+#[test]
+fn reports_colliding_start() {
+    let blocked = with_obstacle(r#"{ type = "sphere", center = [1.0, 1.0, 1.0], radius = 0.5 }"#);
+    let scene = Scene::from_toml_str(&blocked).unwrap();
+    assert_eq!(scene.solve().err().map(|e| e.kind()), Some(ErrorKind::InvalidInput));
+}
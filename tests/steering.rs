@@ -0,0 +1,58 @@
+use std::f64::consts::PI;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use sampling_path_planning::se2::{Pose2, wrap_angle};
+use sampling_path_planning::steering::*;
+
+// As curvas de Dubins e Reeds-Shepp devem terminar exatamente na pose pedida
+
+const RADIUS: f64 = 20.0;
+
+fn random_pose(rng: &mut StdRng) -> Pose2 {
+    Pose2 { x: rng.gen_range(-100.0..100.0), y: rng.gen_range(-100.0..100.0), theta: rng.gen_range(-PI..PI) }
+}
+
+fn assert_reaches(path: &CarPath, a: &Pose2, b: &Pose2) {
+    let end = path.pose_at(a, RADIUS, path.length());
+    assert!(
+        (end.x - b.x).abs() < 1e-6 && (end.y - b.y).abs() < 1e-6 && wrap_angle(end.theta - b.theta).abs() < 1e-6,
+        "curva {:?} de {:?} termina em {:?}, esperado {:?}",
+        path.segments,
+        a,
+        end,
+        b
+    );
+}
+
+#[test]
+fn dubins_paths_reach_the_goal() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..2000 {
+        let (a, b) = (random_pose(&mut rng), random_pose(&mut rng));
+        assert_reaches(&dubins_path(&a, &b, RADIUS), &a, &b);
+    }
+}
+
+#[test]
+fn reeds_shepp_paths_reach_the_goal() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for _ in 0..2000 {
+        let (a, b) = (random_pose(&mut rng), random_pose(&mut rng));
+        let path = reeds_shepp_path(&a, &b, RADIUS).expect("Curva de Reeds-Shepp entre poses finitas");
+        assert_reaches(&path, &a, &b);
+        // Com ré a curva nunca é mais longa que a de Dubins, e o caminho inverso custa o mesmo
+        assert!(path.length() <= dubins_path(&a, &b, RADIUS).length() + 1e-9);
+        let back = reeds_shepp_path(&b, &a, RADIUS).unwrap();
+        assert!((path.length() - back.length()).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn straight_ahead_is_a_single_segment_length() {
+    let a = Pose2 { x: 0.0, y: 0.0, theta: 0.0 };
+    let b = Pose2 { x: 100.0, y: 0.0, theta: 0.0 };
+    assert!((dubins_path(&a, &b, RADIUS).length() * RADIUS - 100.0).abs() < 1e-9);
+    assert!((reeds_shepp_path(&b, &a, RADIUS).unwrap().length() * RADIUS - 100.0).abs() < 1e-9);
+}